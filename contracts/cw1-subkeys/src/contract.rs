@@ -1,12 +1,14 @@
 use schemars::JsonSchema;
+use std::collections::BTreeMap;
 use std::fmt;
-use std::ops::{AddAssign, Sub};
+use std::ops::AddAssign;
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
     ensure, ensure_ne, to_json_binary, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut,
-    DistributionMsg, Empty, Env, MessageInfo, Order, Response, StakingMsg, StdResult,
+    DistributionMsg, Empty, Env, MessageInfo, Order, Response, StakingMsg, StdError, StdResult,
+    Uint128,
 };
 use cw1::CanExecuteResponse;
 use cw1_whitelist::{
@@ -19,7 +21,7 @@ use cw1_whitelist::{
 };
 use cw2::{get_contract_version, set_contract_version};
 use cw_storage_plus::Bound;
-use cw_utils::Expiration;
+use cw_utils::{Expiration, NativeBalance};
 use semver::Version;
 
 use crate::error::ContractError;
@@ -112,7 +114,7 @@ where
                         );
 
                         // Decrease allowance
-                        allowance.balance = allowance.balance.sub(amount.clone())?;
+                        allowance.balance = spend_allowance(&allowance.balance, amount)?;
                         Ok(allowance)
                     })?;
                 }
@@ -130,6 +132,48 @@ where
     Ok(res)
 }
 
+/// Returns the allowance left after spending `amount`. All denoms are checked before
+/// anything is deducted, so the allowance is either fully charged or not at all.
+pub fn spend_allowance(
+    balance: &NativeBalance,
+    amount: &[Coin],
+) -> Result<NativeBalance, ContractError> {
+    // the same denom may appear more than once
+    let mut required: BTreeMap<&str, Uint128> = BTreeMap::new();
+    for coin in amount {
+        let total = required.entry(&coin.denom).or_default();
+        *total = total.checked_add(coin.amount).map_err(StdError::from)?;
+    }
+
+    for (denom, required) in &required {
+        let available = balance
+            .0
+            .iter()
+            .find(|c| c.denom == *denom)
+            .map(|c| c.amount)
+            .unwrap_or_default();
+        ensure!(
+            *required <= available,
+            ContractError::InsufficientAllowance {
+                denom: denom.to_string(),
+                required: *required,
+                available,
+            }
+        );
+    }
+
+    let remaining = balance
+        .0
+        .iter()
+        .map(|c| {
+            let spent = required.get(c.denom.as_str()).copied().unwrap_or_default();
+            Coin::new(c.amount - spent, &c.denom)
+        })
+        .filter(|c| !c.amount.is_zero())
+        .collect();
+    Ok(NativeBalance(remaining))
+}
+
 pub fn check_staking_permissions(
     staking_msg: &StakingMsg,
     permissions: Permissions,
@@ -365,9 +409,8 @@ fn can_execute(deps: Deps, env: Env, sender: String, msg: CosmosMsg) -> StdResul
             let allowance = ALLOWANCES.may_load(deps.storage, &sender)?;
             match allowance {
                 // if there is an allowance, we subtract the requested amount to ensure it is covered (error on underflow)
-                Some(allow) => {
-                    Ok(!allow.expires.is_expired(&env.block) && allow.balance.sub(amount).is_ok())
-                }
+                Some(allow) => Ok(!allow.expires.is_expired(&env.block)
+                    && spend_allowance(&allow.balance, &amount).is_ok()),
                 None => Ok(false),
             }
         }
@@ -1786,6 +1829,85 @@ mod tests {
             );
         }
 
+        #[test]
+        fn multi_denom_all_or_nothing() {
+            let Suite { mut deps, .. } = SuiteConfig::new()
+                .with_allowance(SPENDER1, coin(10, TOKEN1))
+                .with_allowance(SPENDER1, coin(5, TOKEN2))
+                .init();
+
+            // first denom is covered, second one is short
+            let msgs = vec![BankMsg::Send {
+                to_address: SPENDER2.to_owned(),
+                amount: vec![coin(4, TOKEN1), coin(6, TOKEN2)],
+            }
+            .into()];
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(SPENDER1, &[]),
+                ExecuteMsg::Execute { msgs },
+            )
+            .unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::InsufficientAllowance {
+                    denom: TOKEN2.to_owned(),
+                    required: Uint128::new(6),
+                    available: Uint128::new(5),
+                }
+            );
+
+            // the same denom listed twice is summed up
+            let msgs = vec![BankMsg::Send {
+                to_address: SPENDER2.to_owned(),
+                amount: vec![coin(6, TOKEN1), coin(6, TOKEN1)],
+            }
+            .into()];
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(SPENDER1, &[]),
+                ExecuteMsg::Execute { msgs },
+            )
+            .unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::InsufficientAllowance {
+                    denom: TOKEN1.to_owned(),
+                    required: Uint128::new(12),
+                    available: Uint128::new(10),
+                }
+            );
+
+            // nothing was deducted
+            let allowance = query_allowance(deps.as_ref(), mock_env(), SPENDER1.to_owned())
+                .unwrap()
+                .canonical();
+            assert_eq!(
+                allowance.balance,
+                NativeBalance(vec![coin(10, TOKEN1), coin(5, TOKEN2)])
+            );
+
+            // spending all of one denom drops it from the allowance
+            let msgs = vec![BankMsg::Send {
+                to_address: SPENDER2.to_owned(),
+                amount: vec![coin(4, TOKEN1), coin(5, TOKEN2)],
+            }
+            .into()];
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(SPENDER1, &[]),
+                ExecuteMsg::Execute { msgs },
+            )
+            .unwrap();
+            let allowance = query_allowance(deps.as_ref(), mock_env(), SPENDER1.to_owned())
+                .unwrap()
+                .canonical();
+            assert_eq!(allowance.balance, NativeBalance(vec![coin(6, TOKEN1)]));
+        }
+
         #[test]
         fn time_allowance_expired() {
             let Suite { mut deps, .. } = SuiteConfig::new()
@@ -2122,6 +2244,25 @@ mod tests {
             assert_eq!(resp, CanExecuteResponse { can_execute: false });
         }
 
+        #[test]
+        fn multi_denom_not_enough_allowance() {
+            let Suite { deps, .. } = SuiteConfig::new()
+                .with_allowance(SPENDER1, coin(10, TOKEN1))
+                .with_allowance(SPENDER1, coin(5, TOKEN2))
+                .init();
+
+            let msg: CosmosMsg = BankMsg::Send {
+                to_address: SPENDER2.to_owned(),
+                amount: vec![coin(4, TOKEN1), coin(6, TOKEN2)],
+            }
+            .into();
+
+            let resp =
+                query_can_execute(deps.as_ref(), mock_env(), SPENDER1.to_owned(), msg).unwrap();
+
+            assert_eq!(resp, CanExecuteResponse { can_execute: false });
+        }
+
         #[test]
         fn expired_allowance() {
             let Suite { deps, .. } = SuiteConfig::new()
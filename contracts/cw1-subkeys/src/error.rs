@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 use cw_utils::Expiration;
 use thiserror::Error;
 
@@ -37,6 +37,13 @@ pub enum ContractError {
     #[error("Set withdraw address is not allowed")]
     WithdrawAddrPerm {},
 
+    #[error("Insufficient allowance for {denom}: {required} required, {available} available")]
+    InsufficientAllowance {
+        denom: String,
+        required: Uint128,
+        available: Uint128,
+    },
+
     #[error("Unsupported message")]
     UnsupportedMessage {},
 
@@ -4,6 +4,7 @@ use cosmwasm_std::{
 };
 use cw20::{AllowanceResponse, Cw20ReceiveMsg, Expiration};
 
+use crate::contract::burn_hook_msg;
 use crate::error::ContractError;
 use crate::state::{ALLOWANCES, ALLOWANCES_SPENDER, BALANCES, TOKEN_INFO};
 
@@ -92,6 +93,54 @@ pub fn execute_decrease_allowance(
     Ok(res)
 }
 
+pub fn execute_set_allowance_conditional(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    amount: Uint128,
+    expected_current: Uint128,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    if spender_addr == info.sender {
+        return Err(ContractError::CannotSetOwnAccount {});
+    }
+
+    let key = (&info.sender, &spender_addr);
+    let mut allowance = ALLOWANCES.may_load(deps.storage, key)?.unwrap_or_default();
+    if allowance.allowance != expected_current {
+        return Err(ContractError::AllowanceMismatch {
+            expected: expected_current,
+            current: allowance.allowance,
+        });
+    }
+
+    // setting to 0 clears the allowance, like decreasing it below 0 does
+    if amount.is_zero() {
+        ALLOWANCES.remove(deps.storage, key);
+        ALLOWANCES_SPENDER.remove(deps.storage, (&spender_addr, &info.sender));
+    } else {
+        if let Some(exp) = expires {
+            if exp.is_expired(&env.block) {
+                return Err(ContractError::InvalidExpiration {});
+            }
+            allowance.expires = exp;
+        }
+        allowance.allowance = amount;
+        ALLOWANCES.save(deps.storage, key, &allowance)?;
+        ALLOWANCES_SPENDER.save(deps.storage, (&spender_addr, &info.sender), &allowance)?;
+    }
+
+    let res = Response::new().add_attributes(vec![
+        attr("action", "set_allowance_conditional"),
+        attr("owner", info.sender),
+        attr("spender", spender),
+        attr("amount", amount),
+    ]);
+    Ok(res)
+}
+
 // this can be used to update a lower allowance - call bucket.update with proper keys
 pub fn deduct_allowance(
     storage: &mut dyn Storage,
@@ -180,12 +229,13 @@ pub fn execute_burn_from(
         },
     )?;
     // reduce total_supply
-    TOKEN_INFO.update(deps.storage, |mut meta| -> StdResult<_> {
+    let token = TOKEN_INFO.update(deps.storage, |mut meta| -> StdResult<_> {
         meta.total_supply = meta.total_supply.checked_sub(amount)?;
         Ok(meta)
     })?;
 
-    let res = Response::new().add_attributes(vec![
+    let hook = burn_hook_msg(deps.storage, &owner_addr, amount, token.total_supply)?;
+    let res = Response::new().add_messages(hook).add_attributes(vec![
         attr("action", "burn_from"),
         attr("from", owner),
         attr("by", info.sender),
@@ -467,6 +517,91 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_allowance_conditional() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        let owner = deps.api.addr_make("addr0001").to_string();
+        let spender = deps.api.addr_make("addr0002").to_string();
+        let info = mock_info(owner.as_ref(), &[]);
+        let env = mock_env();
+        do_instantiate(deps.as_mut(), &owner, Uint128::new(12340000));
+
+        // set a first allowance, expecting none
+        let expires = Expiration::AtHeight(123_456);
+        let msg = ExecuteMsg::SetAllowanceConditional {
+            spender: spender.clone(),
+            amount: Uint128::new(5000),
+            expected_current: Uint128::zero(),
+            expires: Some(expires),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(
+            res.attributes[0],
+            attr("action", "set_allowance_conditional")
+        );
+        let allowance = query_allowance(deps.as_ref(), owner.clone(), spender.clone()).unwrap();
+        assert_eq!(
+            allowance,
+            AllowanceResponse {
+                allowance: Uint128::new(5000),
+                expires
+            }
+        );
+
+        // the spender uses part of it before the owner's update lands
+        let spender_info = mock_info(spender.as_ref(), &[]);
+        let msg = ExecuteMsg::BurnFrom {
+            owner: owner.clone(),
+            amount: Uint128::new(1000),
+        };
+        execute(deps.as_mut(), env.clone(), spender_info, msg).unwrap();
+
+        // so the update based on the stale value is rejected
+        let msg = ExecuteMsg::SetAllowanceConditional {
+            spender: spender.clone(),
+            amount: Uint128::new(2000),
+            expected_current: Uint128::new(5000),
+            expires: None,
+        };
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::AllowanceMismatch {
+                expected: Uint128::new(5000),
+                current: Uint128::new(4000),
+            }
+        );
+
+        // updating from the real value overwrites the amount, keeping the expiration
+        let msg = ExecuteMsg::SetAllowanceConditional {
+            spender: spender.clone(),
+            amount: Uint128::new(2000),
+            expected_current: Uint128::new(4000),
+            expires: None,
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        let allowance = query_allowance(deps.as_ref(), owner.clone(), spender.clone()).unwrap();
+        assert_eq!(
+            allowance,
+            AllowanceResponse {
+                allowance: Uint128::new(2000),
+                expires
+            }
+        );
+
+        // setting it to zero clears it
+        let msg = ExecuteMsg::SetAllowanceConditional {
+            spender: spender.clone(),
+            amount: Uint128::zero(),
+            expected_current: Uint128::new(2000),
+            expires: None,
+        };
+        execute(deps.as_mut(), env, info, msg).unwrap();
+        let allowance = query_allowance(deps.as_ref(), owner, spender).unwrap();
+        assert_eq!(allowance, AllowanceResponse::default());
+    }
+
     #[test]
     fn no_self_allowance() {
         let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
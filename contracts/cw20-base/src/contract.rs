@@ -2,31 +2,37 @@
 use cosmwasm_std::entry_point;
 use cosmwasm_std::Order::Ascending;
 use cosmwasm_std::{
-    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Uint128,
+    to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, Event, MessageInfo, Reply,
+    Response, StdError, StdResult, Storage, SubMsg, SubMsgResult, Uint128,
 };
 
-use cw2::{ensure_from_older_version, set_contract_version};
+use cw2::{ensure_from_older_version, set_contract_version, CONTRACT};
 use cw20::{
-    BalanceResponse, Cw20Coin, Cw20ReceiveMsg, DownloadLogoResponse, EmbeddedLogo, Logo, LogoInfo,
+    BalanceResponse, BatchSendItem, Cw20BurnHookMsg, Cw20Coin, Cw20ReceiveMsg,
+    DownloadLogoResponse, EmbeddedLogo, FullTokenInfoResponse, Logo, LogoInfo,
     MarketingInfoResponse, MinterResponse, TokenInfoResponse,
 };
 
 use crate::allowances::{
     execute_burn_from, execute_decrease_allowance, execute_increase_allowance, execute_send_from,
-    execute_transfer_from, query_allowance,
+    execute_set_allowance_conditional, execute_transfer_from, query_allowance,
 };
 use crate::enumerable::{query_all_accounts, query_owner_allowances, query_spender_allowances};
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::state::{
-    MinterData, TokenInfo, ALLOWANCES, ALLOWANCES_SPENDER, BALANCES, LOGO, MARKETING_INFO,
-    TOKEN_INFO,
+    MinterData, SendLooseReply, TokenInfo, ALLOWANCES, ALLOWANCES_SPENDER, BALANCES, BURN_HOOK,
+    LOGO, MARKETING_INFO, MARKETING_RENOUNCED, SEND_LOOSE_COUNT, SEND_LOOSE_REPLY, TOKEN_INFO,
 };
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:cw20-base";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Reply ids of `SendLoose` have this bit set, the lower bits hold the key of its
+/// `SEND_LOOSE_REPLY` entry. Contracts importing this one must forward such replies to `reply`.
+pub const SEND_LOOSE_REPLY_FLAG: u64 = 1 << 62;
+
 const LOGO_SIZE_CAP: usize = 5 * 1024;
 
 /// Checks if data starts with XML preamble
@@ -200,6 +206,15 @@ pub fn execute(
             amount,
             msg,
         } => execute_send(deps, env, info, contract, amount, msg),
+        ExecuteMsg::BatchTransfer { transfers } => {
+            execute_batch_transfer(deps, env, info, transfers)
+        }
+        ExecuteMsg::BatchSend { sends } => execute_batch_send(deps, env, info, sends),
+        ExecuteMsg::SendLoose {
+            contract,
+            amount,
+            msg,
+        } => execute_send_loose(deps, env, info, contract, amount, msg),
         ExecuteMsg::Mint { recipient, amount } => execute_mint(deps, env, info, recipient, amount),
         ExecuteMsg::IncreaseAllowance {
             spender,
@@ -211,6 +226,20 @@ pub fn execute(
             amount,
             expires,
         } => execute_decrease_allowance(deps, env, info, spender, amount, expires),
+        ExecuteMsg::SetAllowanceConditional {
+            spender,
+            amount,
+            expected_current,
+            expires,
+        } => execute_set_allowance_conditional(
+            deps,
+            env,
+            info,
+            spender,
+            amount,
+            expected_current,
+            expires,
+        ),
         ExecuteMsg::TransferFrom {
             owner,
             recipient,
@@ -226,12 +255,17 @@ pub fn execute(
         ExecuteMsg::UpdateMarketing {
             project,
             description,
-            marketing,
-        } => execute_update_marketing(deps, env, info, project, description, marketing),
+            // the marketing account is only changed by UpdateMarketingAdmin now
+            marketing: _,
+        } => execute_update_marketing(deps, env, info, project, description),
+        ExecuteMsg::UpdateMarketingAdmin { admin } => {
+            execute_update_marketing_admin(deps, env, info, admin)
+        }
         ExecuteMsg::UploadLogo(logo) => execute_upload_logo(deps, env, info, logo),
         ExecuteMsg::UpdateMinter { new_minter } => {
             execute_update_minter(deps, env, info, new_minter)
         }
+        ExecuteMsg::UpdateBurnHook { hook } => execute_update_burn_hook(deps, env, info, hook),
     }
 }
 
@@ -280,18 +314,41 @@ pub fn execute_burn(
         },
     )?;
     // reduce total_supply
-    TOKEN_INFO.update(deps.storage, |mut info| -> StdResult<_> {
+    let token = TOKEN_INFO.update(deps.storage, |mut info| -> StdResult<_> {
         info.total_supply = info.total_supply.checked_sub(amount)?;
         Ok(info)
     })?;
 
+    let hook = burn_hook_msg(deps.storage, &info.sender, amount, token.total_supply)?;
     let res = Response::new()
+        .add_messages(hook)
         .add_attribute("action", "burn")
         .add_attribute("from", info.sender)
         .add_attribute("amount", amount);
     Ok(res)
 }
 
+/// Builds the message for the registered burn hook, if any. It is dispatched along with the
+/// burn, so an error returned by the hook reverts the burn as well.
+pub(crate) fn burn_hook_msg(
+    storage: &dyn Storage,
+    burner: &Addr,
+    amount: Uint128,
+    remaining_supply: Uint128,
+) -> StdResult<Option<CosmosMsg>> {
+    BURN_HOOK
+        .may_load(storage)?
+        .map(|hook| {
+            Cw20BurnHookMsg {
+                burner: burner.to_string(),
+                amount,
+                remaining_supply,
+            }
+            .into_cosmos_msg(hook)
+        })
+        .transpose()
+}
+
 pub fn execute_mint(
     deps: DepsMut,
     _env: Env,
@@ -377,6 +434,146 @@ pub fn execute_send(
     Ok(res)
 }
 
+/// Like `execute_send`, but keeps the transfer if the receiving contract fails.
+/// See `reply` for the handling of such a failure.
+pub fn execute_send_loose(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    contract: String,
+    amount: Uint128,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let rcpt_addr = deps.api.addr_validate(&contract)?;
+
+    // move the tokens to the contract
+    BALANCES.update(
+        deps.storage,
+        &info.sender,
+        |balance: Option<Uint128>| -> StdResult<_> {
+            Ok(balance.unwrap_or_default().checked_sub(amount)?)
+        },
+    )?;
+    BALANCES.update(
+        deps.storage,
+        &rcpt_addr,
+        |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
+    )?;
+
+    let receive = Cw20ReceiveMsg {
+        sender: info.sender.to_string(),
+        amount,
+        msg,
+    }
+    .into_cosmos_msg(&rcpt_addr)?;
+    // keyed per send, as the receiver may call SendLoose again before our reply runs
+    let key = SEND_LOOSE_COUNT.may_load(deps.storage)?.unwrap_or_default();
+    SEND_LOOSE_COUNT.save(deps.storage, &(key + 1))?;
+    SEND_LOOSE_REPLY.save(
+        deps.storage,
+        key,
+        &SendLooseReply {
+            from: info.sender.clone(),
+            to: rcpt_addr,
+            amount,
+        },
+    )?;
+
+    let res = Response::new()
+        .add_attribute("action", "send_loose")
+        .add_attribute("from", info.sender)
+        .add_attribute("to", contract)
+        .add_attribute("amount", amount)
+        .add_submessage(SubMsg::reply_always(receive, SEND_LOOSE_REPLY_FLAG | key));
+    Ok(res)
+}
+
+pub fn execute_batch_transfer(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    transfers: Vec<Cw20Coin>,
+) -> Result<Response, ContractError> {
+    let mut res = Response::new()
+        .add_attribute("action", "batch_transfer")
+        .add_attribute("from", &info.sender);
+
+    // validate all recipients and deduct the whole sum up front, so that the batch
+    // either fully succeeds or leaves no trace
+    let mut total = Uint128::zero();
+    let mut recipients = Vec::with_capacity(transfers.len());
+    for transfer in transfers {
+        recipients.push((deps.api.addr_validate(&transfer.address)?, transfer.amount));
+        total = total.checked_add(transfer.amount).map_err(StdError::from)?;
+    }
+    BALANCES.update(
+        deps.storage,
+        &info.sender,
+        |balance: Option<Uint128>| -> StdResult<_> {
+            Ok(balance.unwrap_or_default().checked_sub(total)?)
+        },
+    )?;
+
+    for (rcpt_addr, amount) in recipients {
+        BALANCES.update(
+            deps.storage,
+            &rcpt_addr,
+            |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
+        )?;
+        res = res
+            .add_attribute("to", rcpt_addr)
+            .add_attribute("amount", amount);
+    }
+    Ok(res)
+}
+
+pub fn execute_batch_send(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    sends: Vec<BatchSendItem>,
+) -> Result<Response, ContractError> {
+    let mut res = Response::new()
+        .add_attribute("action", "batch_send")
+        .add_attribute("from", &info.sender);
+
+    // same as batch transfer: validate everything and deduct the sum before crediting
+    let mut total = Uint128::zero();
+    let mut recipients = Vec::with_capacity(sends.len());
+    for send in sends {
+        let rcpt_addr = deps.api.addr_validate(&send.contract)?;
+        total = total.checked_add(send.amount).map_err(StdError::from)?;
+        recipients.push((rcpt_addr, send.amount, send.msg));
+    }
+    BALANCES.update(
+        deps.storage,
+        &info.sender,
+        |balance: Option<Uint128>| -> StdResult<_> {
+            Ok(balance.unwrap_or_default().checked_sub(total)?)
+        },
+    )?;
+
+    for (rcpt_addr, amount, msg) in recipients {
+        BALANCES.update(
+            deps.storage,
+            &rcpt_addr,
+            |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
+        )?;
+        res = res
+            .add_attribute("to", &rcpt_addr)
+            .add_attribute("amount", amount)
+            .add_message(
+                Cw20ReceiveMsg {
+                    sender: info.sender.to_string(),
+                    amount,
+                    msg,
+                }
+                .into_cosmos_msg(rcpt_addr)?,
+            );
+    }
+    Ok(res)
+}
+
 pub fn execute_update_minter(
     deps: DepsMut,
     _env: Env,
@@ -415,14 +612,50 @@ pub fn execute_update_minter(
         ))
 }
 
+pub fn execute_update_burn_hook(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    hook: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = TOKEN_INFO
+        .may_load(deps.storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+
+    // the hook may always remove itself, so it does not outlive a removed minter for good
+    let is_minter = config
+        .mint
+        .as_ref()
+        .is_some_and(|mint| mint.minter == info.sender);
+    let is_hook_leaving =
+        hook.is_none() && BURN_HOOK.may_load(deps.storage)?.as_ref() == Some(&info.sender);
+    if !is_minter && !is_hook_leaving {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let hook = hook.map(|hook| deps.api.addr_validate(&hook)).transpose()?;
+    match &hook {
+        Some(hook) => BURN_HOOK.save(deps.storage, hook)?,
+        None => BURN_HOOK.remove(deps.storage),
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "update_burn_hook")
+        .add_attribute(
+            "new_hook",
+            hook.map(Addr::into_string)
+                .unwrap_or_else(|| "None".to_string()),
+        ))
+}
+
 pub fn execute_update_marketing(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
     project: Option<String>,
     description: Option<String>,
-    marketing: Option<String>,
 ) -> Result<Response, ContractError> {
+    ensure_marketing_not_renounced(deps.storage)?;
     let mut marketing_info = MARKETING_INFO
         .may_load(deps.storage)?
         .ok_or(ContractError::Unauthorized {})?;
@@ -448,12 +681,6 @@ pub fn execute_update_marketing(
         None => (),
     }
 
-    match marketing {
-        Some(empty) if empty.trim().is_empty() => marketing_info.marketing = None,
-        Some(marketing) => marketing_info.marketing = Some(deps.api.addr_validate(&marketing)?),
-        None => (),
-    }
-
     if marketing_info.project.is_none()
         && marketing_info.description.is_none()
         && marketing_info.marketing.is_none()
@@ -468,12 +695,60 @@ pub fn execute_update_marketing(
     Ok(res)
 }
 
+pub fn execute_update_marketing_admin(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    admin: Option<String>,
+) -> Result<Response, ContractError> {
+    ensure_marketing_not_renounced(deps.storage)?;
+    let mut marketing_info = MARKETING_INFO
+        .may_load(deps.storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+
+    let old_admin = marketing_info
+        .marketing
+        .ok_or(ContractError::Unauthorized {})?;
+    if old_admin != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    marketing_info.marketing = admin
+        .as_deref()
+        .map(|admin| deps.api.addr_validate(admin))
+        .transpose()?;
+    if marketing_info.marketing.is_none() {
+        MARKETING_RENOUNCED.save(deps.storage, &true)?;
+    }
+    MARKETING_INFO.save(deps.storage, &marketing_info)?;
+
+    let res = Response::new()
+        .add_attribute("action", "update_marketing_admin")
+        .add_attribute("old_admin", old_admin)
+        .add_attribute(
+            "new_admin",
+            marketing_info
+                .marketing
+                .map(Addr::into_string)
+                .unwrap_or_else(|| "None".to_string()),
+        );
+    Ok(res)
+}
+
+fn ensure_marketing_not_renounced(storage: &dyn Storage) -> Result<(), ContractError> {
+    if MARKETING_RENOUNCED.may_load(storage)?.unwrap_or_default() {
+        return Err(ContractError::MarketingRenounced {});
+    }
+    Ok(())
+}
+
 pub fn execute_upload_logo(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
     logo: Logo,
 ) -> Result<Response, ContractError> {
+    ensure_marketing_not_renounced(deps.storage)?;
     let mut marketing_info = MARKETING_INFO
         .may_load(deps.storage)?
         .ok_or(ContractError::Unauthorized {})?;
@@ -503,11 +778,39 @@ pub fn execute_upload_logo(
     Ok(res)
 }
 
+/// Called after the receiver of a `SendLoose` ran. If it failed, its state changes are
+/// reverted, but the tokens stay with it, so we just record the failure.
+/// Handles the reply ids with `SEND_LOOSE_REPLY_FLAG` set and rejects all others.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if msg.id & SEND_LOOSE_REPLY_FLAG == 0 {
+        return Err(ContractError::UnknownReplyId { id: msg.id });
+    }
+    let key = msg.id & !SEND_LOOSE_REPLY_FLAG;
+    let error = match msg.result {
+        SubMsgResult::Err(err) => err,
+        SubMsgResult::Ok(_) => {
+            SEND_LOOSE_REPLY.remove(deps.storage, key);
+            return Ok(Response::new());
+        }
+    };
+    let SendLooseReply { from, to, amount } = SEND_LOOSE_REPLY.load(deps.storage, key)?;
+    SEND_LOOSE_REPLY.remove(deps.storage, key);
+
+    let event = Event::new("cw20_send_loose_failed")
+        .add_attribute("from", from)
+        .add_attribute("to", to)
+        .add_attribute("amount", amount)
+        .add_attribute("error", error);
+    Ok(Response::new().add_event(event))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Balance { address } => to_json_binary(&query_balance(deps, address)?),
         QueryMsg::TokenInfo {} => to_json_binary(&query_token_info(deps)?),
+        QueryMsg::FullTokenInfo {} => to_json_binary(&query_full_token_info(deps)?),
         QueryMsg::Minter {} => to_json_binary(&query_minter(deps)?),
         QueryMsg::Allowance { owner, spender } => {
             to_json_binary(&query_allowance(deps, owner, spender)?)
@@ -554,6 +857,15 @@ pub fn query_token_info(deps: Deps) -> StdResult<TokenInfoResponse> {
     Ok(res)
 }
 
+pub fn query_full_token_info(deps: Deps) -> StdResult<FullTokenInfoResponse> {
+    Ok(FullTokenInfoResponse {
+        token_info: query_token_info(deps)?,
+        minter: query_minter(deps)?,
+        marketing: MARKETING_INFO.may_load(deps.storage)?,
+        version: CONTRACT.may_load(deps.storage)?,
+    })
+}
+
 pub fn query_minter(deps: Deps) -> StdResult<Option<MinterResponse>> {
     let meta = TOKEN_INFO.load(deps.storage)?;
     let minter = match meta.mint {
@@ -607,7 +919,7 @@ mod tests {
     use cosmwasm_std::testing::{
         mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info,
     };
-    use cosmwasm_std::{coins, from_json, Addr, CosmosMsg, StdError, SubMsg, WasmMsg};
+    use cosmwasm_std::{attr, coins, from_json, Addr, CosmosMsg, StdError, SubMsg, WasmMsg};
 
     use super::*;
     use crate::msg::InstantiateMarketingInfo;
@@ -680,6 +992,61 @@ mod tests {
 
     const PNG_HEADER: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
 
+    #[test]
+    fn full_token_info_combines_queries() {
+        // minimal token
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner").to_string();
+        do_instantiate(deps.as_mut(), &owner, Uint128::new(1000));
+
+        let full = query_full_token_info(deps.as_ref()).unwrap();
+        assert_eq!(full.token_info, query_token_info(deps.as_ref()).unwrap());
+        assert_eq!(full.minter, None);
+        assert_eq!(full.marketing, None);
+        assert_eq!(
+            full.version,
+            Some(cw2::get_contract_version(deps.as_ref().storage).unwrap())
+        );
+
+        // populated token
+        let mut deps = mock_dependencies();
+        let minter = deps.api.addr_make("minter").to_string();
+        let marketing = deps.api.addr_make("marketing").to_string();
+        let instantiate_msg = InstantiateMsg {
+            name: "Cash Token".to_string(),
+            symbol: "CASH".to_string(),
+            decimals: 9,
+            initial_balances: vec![],
+            mint: Some(MinterResponse {
+                minter,
+                cap: Some(Uint128::new(5000)),
+            }),
+            marketing: Some(InstantiateMarketingInfo {
+                project: Some("Project".to_owned()),
+                description: Some("Description".to_owned()),
+                marketing: Some(marketing),
+                logo: Some(Logo::Url("url".to_owned())),
+            }),
+        };
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            instantiate_msg,
+        )
+        .unwrap();
+
+        let full = query_full_token_info(deps.as_ref()).unwrap();
+        assert_eq!(full.token_info, query_token_info(deps.as_ref()).unwrap());
+        assert_eq!(full.minter, query_minter(deps.as_ref()).unwrap());
+        assert!(full.minter.is_some());
+        assert_eq!(
+            full.marketing,
+            Some(query_marketing_info(deps.as_ref()).unwrap())
+        );
+        assert_eq!(full.version.unwrap().contract, CONTRACT_NAME);
+    }
+
     mod instantiate {
         use super::*;
 
@@ -1312,6 +1679,168 @@ mod tests {
         );
     }
 
+    #[test]
+    fn batch_transfer() {
+        let mut deps = mock_dependencies();
+        let addr1 = deps.api.addr_make("addr0001").to_string();
+        let addr2 = deps.api.addr_make("addr0002").to_string();
+        let addr3 = deps.api.addr_make("addr0003").to_string();
+        let amount1 = Uint128::from(12340000u128);
+
+        do_instantiate(deps.as_mut(), &addr1, amount1);
+
+        // fails atomically if the sum exceeds the balance, even if each entry fits
+        let info = mock_info(addr1.as_ref(), &[]);
+        let msg = ExecuteMsg::BatchTransfer {
+            transfers: vec![
+                Cw20Coin {
+                    address: addr2.clone(),
+                    amount: Uint128::new(12000000),
+                },
+                Cw20Coin {
+                    address: addr3.clone(),
+                    amount: Uint128::new(1000000),
+                },
+            ],
+        };
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+        assert_eq!(get_balance(deps.as_ref(), &addr1), amount1);
+        assert_eq!(get_balance(deps.as_ref(), &addr2), Uint128::zero());
+
+        // invalid recipient fails the whole batch
+        let info = mock_info(addr1.as_ref(), &[]);
+        let msg = ExecuteMsg::BatchTransfer {
+            transfers: vec![
+                Cw20Coin {
+                    address: addr2.clone(),
+                    amount: Uint128::new(100),
+                },
+                Cw20Coin {
+                    address: "".to_string(),
+                    amount: Uint128::new(100),
+                },
+            ],
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(get_balance(deps.as_ref(), &addr1), amount1);
+
+        // valid batch, including the same recipient twice
+        let info = mock_info(addr1.as_ref(), &[]);
+        let msg = ExecuteMsg::BatchTransfer {
+            transfers: vec![
+                Cw20Coin {
+                    address: addr2.clone(),
+                    amount: Uint128::new(100),
+                },
+                Cw20Coin {
+                    address: addr3.clone(),
+                    amount: Uint128::new(200),
+                },
+                Cw20Coin {
+                    address: addr2.clone(),
+                    amount: Uint128::new(300),
+                },
+            ],
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(res.messages.len(), 0);
+        assert_eq!(res.attributes[0], attr("action", "batch_transfer"));
+        assert_eq!(res.attributes.len(), 2 + 3 * 2);
+
+        assert_eq!(
+            get_balance(deps.as_ref(), &addr1),
+            amount1 - Uint128::new(600)
+        );
+        assert_eq!(get_balance(deps.as_ref(), &addr2), Uint128::new(400));
+        assert_eq!(get_balance(deps.as_ref(), &addr3), Uint128::new(200));
+        assert_eq!(
+            query_token_info(deps.as_ref()).unwrap().total_supply,
+            amount1
+        );
+    }
+
+    #[test]
+    fn batch_send() {
+        let mut deps = mock_dependencies();
+        let addr1 = deps.api.addr_make("addr0001").to_string();
+        let contract1 = deps.api.addr_make("contract0001").to_string();
+        let contract2 = deps.api.addr_make("contract0002").to_string();
+        let amount1 = Uint128::from(12340000u128);
+        let send_msg1 = Binary::from(r#"{"some":123}"#.as_bytes());
+        let send_msg2 = Binary::from(r#"{"other":456}"#.as_bytes());
+
+        do_instantiate(deps.as_mut(), &addr1, amount1);
+
+        // cannot send more than we have in total
+        let info = mock_info(addr1.as_ref(), &[]);
+        let msg = ExecuteMsg::BatchSend {
+            sends: vec![
+                BatchSendItem {
+                    contract: contract1.clone(),
+                    amount: amount1,
+                    msg: send_msg1.clone(),
+                },
+                BatchSendItem {
+                    contract: contract2.clone(),
+                    amount: Uint128::new(1),
+                    msg: send_msg2.clone(),
+                },
+            ],
+        };
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+        assert_eq!(get_balance(deps.as_ref(), &addr1), amount1);
+
+        // valid batch notifies every contract in order
+        let info = mock_info(addr1.as_ref(), &[]);
+        let msg = ExecuteMsg::BatchSend {
+            sends: vec![
+                BatchSendItem {
+                    contract: contract1.clone(),
+                    amount: Uint128::new(1000),
+                    msg: send_msg1.clone(),
+                },
+                BatchSendItem {
+                    contract: contract2.clone(),
+                    amount: Uint128::new(2000),
+                    msg: send_msg2.clone(),
+                },
+            ],
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(
+                    Cw20ReceiveMsg {
+                        sender: addr1.clone(),
+                        amount: Uint128::new(1000),
+                        msg: send_msg1,
+                    }
+                    .into_cosmos_msg(&contract1)
+                    .unwrap()
+                ),
+                SubMsg::new(
+                    Cw20ReceiveMsg {
+                        sender: addr1.clone(),
+                        amount: Uint128::new(2000),
+                        msg: send_msg2,
+                    }
+                    .into_cosmos_msg(&contract2)
+                    .unwrap()
+                ),
+            ]
+        );
+
+        assert_eq!(
+            get_balance(deps.as_ref(), &addr1),
+            amount1 - Uint128::new(3000)
+        );
+        assert_eq!(get_balance(deps.as_ref(), contract1), Uint128::new(1000));
+        assert_eq!(get_balance(deps.as_ref(), contract2), Uint128::new(2000));
+    }
+
     mod migration {
         use super::*;
 
@@ -1435,6 +1964,460 @@ mod tests {
         }
     }
 
+    mod burn_hook {
+        use super::*;
+
+        use cosmwasm_schema::cw_serde;
+        use cosmwasm_std::{Empty, Event};
+        use cw20::{AllowanceResponse, MinterResponse};
+        use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+
+        #[cw_serde]
+        enum HookExecuteMsg {
+            BeforeBurn(Cw20BurnHookMsg),
+        }
+
+        // accepts burns of up to 100 tokens
+        fn hook_execute(
+            _deps: DepsMut,
+            _env: Env,
+            _info: MessageInfo,
+            msg: HookExecuteMsg,
+        ) -> StdResult<Response> {
+            let HookExecuteMsg::BeforeBurn(burn) = msg;
+            if burn.amount > Uint128::new(100) {
+                return Err(StdError::generic_err("burn vetoed"));
+            }
+            Ok(Response::new()
+                .add_attribute("burner", burn.burner)
+                .add_attribute("remaining_supply", burn.remaining_supply))
+        }
+
+        fn hook_contract() -> Box<dyn Contract<Empty>> {
+            let contract = ContractWrapper::new(
+                hook_execute,
+                |_, _, _, _: Empty| -> StdResult<Response> { Ok(Response::new()) },
+                |_, _, _: Empty| -> StdResult<Binary> { Ok(Binary::default()) },
+            );
+            Box::new(contract)
+        }
+
+        fn cw20_contract() -> Box<dyn Contract<Empty>> {
+            let contract = ContractWrapper::new(
+                crate::contract::execute,
+                crate::contract::instantiate,
+                crate::contract::query,
+            );
+            Box::new(contract)
+        }
+
+        fn supply(app: &App, cw20_addr: &Addr) -> Uint128 {
+            let info: TokenInfoResponse = app
+                .wrap()
+                .query_wasm_smart(cw20_addr, &QueryMsg::TokenInfo {})
+                .unwrap();
+            info.total_supply
+        }
+
+        fn balance(app: &App, cw20_addr: &Addr, address: &Addr) -> Uint128 {
+            let res: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw20_addr,
+                    &QueryMsg::Balance {
+                        address: address.to_string(),
+                    },
+                )
+                .unwrap();
+            res.balance
+        }
+
+        #[test]
+        fn hook_can_veto_burns() {
+            let mut app = App::default();
+            let minter = app.api().addr_make("minter");
+            let owner = app.api().addr_make("owner");
+            let spender = app.api().addr_make("spender");
+
+            let cw20_id = app.store_code(cw20_contract());
+            let cw20_addr = app
+                .instantiate_contract(
+                    cw20_id,
+                    minter.clone(),
+                    &InstantiateMsg {
+                        name: "Token".to_string(),
+                        symbol: "TOKEN".to_string(),
+                        decimals: 6,
+                        initial_balances: vec![Cw20Coin {
+                            address: owner.to_string(),
+                            amount: Uint128::new(1000),
+                        }],
+                        mint: Some(MinterResponse {
+                            minter: minter.to_string(),
+                            cap: None,
+                        }),
+                        marketing: None,
+                    },
+                    &[],
+                    "TOKEN",
+                    None,
+                )
+                .unwrap();
+            let hook_id = app.store_code(hook_contract());
+            let hook_addr = app
+                .instantiate_contract(hook_id, minter.clone(), &Empty {}, &[], "HOOK", None)
+                .unwrap();
+
+            // only the minter can register the hook
+            let msg = ExecuteMsg::UpdateBurnHook {
+                hook: Some(hook_addr.to_string()),
+            };
+            let err = app
+                .execute_contract(owner.clone(), cw20_addr.clone(), &msg, &[])
+                .unwrap_err();
+            assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+            app.execute_contract(minter.clone(), cw20_addr.clone(), &msg, &[])
+                .unwrap();
+
+            // accepted burn is passed the remaining supply
+            let msg = ExecuteMsg::Burn {
+                amount: Uint128::new(100),
+            };
+            let res = app
+                .execute_contract(owner.clone(), cw20_addr.clone(), &msg, &[])
+                .unwrap();
+            assert!(res.has_event(
+                &Event::new("wasm")
+                    .add_attribute("_contract_address", &hook_addr)
+                    .add_attribute("burner", &owner)
+                    .add_attribute("remaining_supply", "900")
+            ));
+            assert_eq!(supply(&app, &cw20_addr), Uint128::new(900));
+
+            // vetoed burn is rolled back completely
+            let msg = ExecuteMsg::Burn {
+                amount: Uint128::new(101),
+            };
+            let err = app
+                .execute_contract(owner.clone(), cw20_addr.clone(), &msg, &[])
+                .unwrap_err();
+            assert!(err.root_cause().to_string().contains("burn vetoed"));
+            assert_eq!(supply(&app, &cw20_addr), Uint128::new(900));
+            assert_eq!(balance(&app, &cw20_addr, &owner), Uint128::new(900));
+
+            // same for burning from an allowance, which is not used up either
+            let msg = ExecuteMsg::IncreaseAllowance {
+                spender: spender.to_string(),
+                amount: Uint128::new(500),
+                expires: None,
+            };
+            app.execute_contract(owner.clone(), cw20_addr.clone(), &msg, &[])
+                .unwrap();
+            let msg = ExecuteMsg::BurnFrom {
+                owner: owner.to_string(),
+                amount: Uint128::new(500),
+            };
+            let err = app
+                .execute_contract(spender.clone(), cw20_addr.clone(), &msg, &[])
+                .unwrap_err();
+            assert!(err.root_cause().to_string().contains("burn vetoed"));
+            assert_eq!(supply(&app, &cw20_addr), Uint128::new(900));
+            let allowance: AllowanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    &cw20_addr,
+                    &QueryMsg::Allowance {
+                        owner: owner.to_string(),
+                        spender: spender.to_string(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(allowance.allowance, Uint128::new(500));
+
+            // without the hook, the burn goes through
+            let msg = ExecuteMsg::UpdateBurnHook { hook: None };
+            app.execute_contract(minter, cw20_addr.clone(), &msg, &[])
+                .unwrap();
+            let msg = ExecuteMsg::BurnFrom {
+                owner: owner.to_string(),
+                amount: Uint128::new(500),
+            };
+            app.execute_contract(spender, cw20_addr.clone(), &msg, &[])
+                .unwrap();
+            assert_eq!(supply(&app, &cw20_addr), Uint128::new(400));
+            assert_eq!(balance(&app, &cw20_addr, &owner), Uint128::new(400));
+        }
+
+        #[test]
+        fn hook_can_remove_itself() {
+            let mut deps = mock_dependencies();
+            let owner = deps.api.addr_make("owner").to_string();
+            let minter = deps.api.addr_make("minter").to_string();
+            let hook = deps.api.addr_make("hook").to_string();
+            do_instantiate_with_minter(deps.as_mut(), &owner, Uint128::new(1000), &minter, None);
+
+            let msg = ExecuteMsg::UpdateBurnHook {
+                hook: Some(hook.clone()),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info(&minter, &[]), msg).unwrap();
+            let msg = ExecuteMsg::UpdateMinter { new_minter: None };
+            execute(deps.as_mut(), mock_env(), mock_info(&minter, &[]), msg).unwrap();
+
+            // without a minter, nobody else can touch the hook
+            let msg = ExecuteMsg::UpdateBurnHook { hook: None };
+            let err = execute(deps.as_mut(), mock_env(), mock_info(&owner, &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::Unauthorized {});
+
+            // the hook cannot replace itself, only leave
+            let msg = ExecuteMsg::UpdateBurnHook {
+                hook: Some(owner.clone()),
+            };
+            let err = execute(deps.as_mut(), mock_env(), mock_info(&hook, &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::Unauthorized {});
+
+            let msg = ExecuteMsg::UpdateBurnHook { hook: None };
+            execute(deps.as_mut(), mock_env(), mock_info(&hook, &[]), msg).unwrap();
+            assert_eq!(BURN_HOOK.may_load(&deps.storage).unwrap(), None);
+        }
+    }
+
+    mod send_loose {
+        use super::*;
+
+        use cosmwasm_schema::cw_serde;
+        use cosmwasm_std::Empty;
+        use cw20::MinterResponse;
+        use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+
+        #[cw_serde]
+        enum ReceiverExecuteMsg {
+            Receive(Cw20ReceiveMsg),
+        }
+
+        // accepts notifications with an empty msg and fails on "fail". Any other msg is the
+        // address of another receiver to pass the tokens on to, with a loose send that fails
+        fn receiver_execute(
+            _deps: DepsMut,
+            _env: Env,
+            info: MessageInfo,
+            msg: ReceiverExecuteMsg,
+        ) -> StdResult<Response> {
+            let ReceiverExecuteMsg::Receive(receive) = msg;
+            if receive.msg.is_empty() {
+                return Ok(Response::new());
+            }
+            if receive.msg.as_slice() == b"fail" {
+                return Err(StdError::generic_err("receive failed"));
+            }
+            let forward = ExecuteMsg::SendLoose {
+                contract: from_json(&receive.msg)?,
+                amount: receive.amount,
+                msg: Binary::from(b"fail"),
+            };
+            Ok(Response::new().add_message(WasmMsg::Execute {
+                contract_addr: info.sender.into_string(),
+                msg: to_json_binary(&forward)?,
+                funds: vec![],
+            }))
+        }
+
+        fn receiver_contract() -> Box<dyn Contract<Empty>> {
+            let contract = ContractWrapper::new(
+                receiver_execute,
+                |_, _, _, _: Empty| -> StdResult<Response> { Ok(Response::new()) },
+                |_, _, _: Empty| -> StdResult<Binary> { Ok(Binary::default()) },
+            );
+            Box::new(contract)
+        }
+
+        fn cw20_contract() -> Box<dyn Contract<Empty>> {
+            let contract = ContractWrapper::new(
+                crate::contract::execute,
+                crate::contract::instantiate,
+                crate::contract::query,
+            )
+            .with_reply(crate::contract::reply);
+            Box::new(contract)
+        }
+
+        fn has_pending_send(app: &App, cw20_addr: &Addr, key: u64) -> bool {
+            app.wrap()
+                .query_wasm_raw(cw20_addr, SEND_LOOSE_REPLY.key(key).to_vec())
+                .unwrap()
+                .is_some()
+        }
+
+        fn balance(app: &App, cw20_addr: &Addr, address: &Addr) -> Uint128 {
+            let res: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw20_addr,
+                    &QueryMsg::Balance {
+                        address: address.to_string(),
+                    },
+                )
+                .unwrap();
+            res.balance
+        }
+
+        #[test]
+        fn failing_receiver_keeps_tokens() {
+            let mut app = App::default();
+            let owner = app.api().addr_make("owner");
+
+            let cw20_id = app.store_code(cw20_contract());
+            let cw20_addr = app
+                .instantiate_contract(
+                    cw20_id,
+                    owner.clone(),
+                    &InstantiateMsg {
+                        name: "Token".to_string(),
+                        symbol: "TOKEN".to_string(),
+                        decimals: 6,
+                        initial_balances: vec![Cw20Coin {
+                            address: owner.to_string(),
+                            amount: Uint128::new(1000),
+                        }],
+                        mint: None::<MinterResponse>,
+                        marketing: None,
+                    },
+                    &[],
+                    "TOKEN",
+                    None,
+                )
+                .unwrap();
+            let receiver_id = app.store_code(receiver_contract());
+            let receiver = app
+                .instantiate_contract(receiver_id, owner.clone(), &Empty {}, &[], "RCV", None)
+                .unwrap();
+
+            // a normal send to a failing receiver is reverted
+            let msg = ExecuteMsg::Send {
+                contract: receiver.to_string(),
+                amount: Uint128::new(100),
+                msg: Binary::from(b"fail"),
+            };
+            let err = app
+                .execute_contract(owner.clone(), cw20_addr.clone(), &msg, &[])
+                .unwrap_err();
+            assert!(err.root_cause().to_string().contains("receive failed"));
+            assert_eq!(balance(&app, &cw20_addr, &owner), Uint128::new(1000));
+            assert_eq!(balance(&app, &cw20_addr, &receiver), Uint128::zero());
+
+            // a loose send keeps the transfer and records the failure
+            let msg = ExecuteMsg::SendLoose {
+                contract: receiver.to_string(),
+                amount: Uint128::new(100),
+                msg: Binary::from(b"fail"),
+            };
+            let res = app
+                .execute_contract(owner.clone(), cw20_addr.clone(), &msg, &[])
+                .unwrap();
+            let failed = res
+                .events
+                .iter()
+                .find(|e| e.ty == "wasm-cw20_send_loose_failed")
+                .expect("failure event");
+            let attr = |key: &str| {
+                failed
+                    .attributes
+                    .iter()
+                    .find(|a| a.key == key)
+                    .map(|a| a.value.clone())
+                    .unwrap()
+            };
+            assert_eq!(attr("from"), owner.to_string());
+            assert_eq!(attr("to"), receiver.to_string());
+            assert_eq!(attr("amount"), "100");
+            assert!(attr("error").contains("receive failed"));
+            assert_eq!(balance(&app, &cw20_addr, &owner), Uint128::new(900));
+            assert_eq!(balance(&app, &cw20_addr, &receiver), Uint128::new(100));
+            assert!(!has_pending_send(&app, &cw20_addr, 0));
+
+            // a successful loose send emits no failure
+            let msg = ExecuteMsg::SendLoose {
+                contract: receiver.to_string(),
+                amount: Uint128::new(50),
+                msg: Binary::default(),
+            };
+            let res = app
+                .execute_contract(owner.clone(), cw20_addr.clone(), &msg, &[])
+                .unwrap();
+            assert!(!res
+                .events
+                .iter()
+                .any(|e| e.ty == "wasm-cw20_send_loose_failed"));
+            assert_eq!(balance(&app, &cw20_addr, &receiver), Uint128::new(150));
+            assert!(!has_pending_send(&app, &cw20_addr, 1));
+        }
+
+        #[test]
+        fn nested_send_loose() {
+            let mut app = App::default();
+            let owner = app.api().addr_make("owner");
+
+            let cw20_id = app.store_code(cw20_contract());
+            let cw20_addr = app
+                .instantiate_contract(
+                    cw20_id,
+                    owner.clone(),
+                    &InstantiateMsg {
+                        name: "Token".to_string(),
+                        symbol: "TOKEN".to_string(),
+                        decimals: 6,
+                        initial_balances: vec![Cw20Coin {
+                            address: owner.to_string(),
+                            amount: Uint128::new(1000),
+                        }],
+                        mint: None::<MinterResponse>,
+                        marketing: None,
+                    },
+                    &[],
+                    "TOKEN",
+                    None,
+                )
+                .unwrap();
+            let receiver_id = app.store_code(receiver_contract());
+            let forwarder = app
+                .instantiate_contract(receiver_id, owner.clone(), &Empty {}, &[], "FWD", None)
+                .unwrap();
+            let sink = app
+                .instantiate_contract(receiver_id, owner.clone(), &Empty {}, &[], "SINK", None)
+                .unwrap();
+
+            // the forwarder sends loosely to the failing sink before our reply to it runs
+            let msg = ExecuteMsg::SendLoose {
+                contract: forwarder.to_string(),
+                amount: Uint128::new(100),
+                msg: to_json_binary(&sink).unwrap(),
+            };
+            let res = app
+                .execute_contract(owner.clone(), cw20_addr.clone(), &msg, &[])
+                .unwrap();
+
+            // only the inner send failed, and it is reported with its own transfer
+            let failed: Vec<_> = res
+                .events
+                .iter()
+                .filter(|e| e.ty == "wasm-cw20_send_loose_failed")
+                .collect();
+            assert_eq!(failed.len(), 1);
+            assert!(failed[0]
+                .attributes
+                .iter()
+                .any(|a| a.key == "from" && a.value == forwarder.as_str()));
+            assert!(failed[0]
+                .attributes
+                .iter()
+                .any(|a| a.key == "to" && a.value == sink.as_str()));
+            assert_eq!(balance(&app, &cw20_addr, &owner), Uint128::new(900));
+            assert_eq!(balance(&app, &cw20_addr, &forwarder), Uint128::zero());
+            assert_eq!(balance(&app, &cw20_addr, &sink), Uint128::new(100));
+            assert!(!has_pending_send(&app, &cw20_addr, 0));
+            assert!(!has_pending_send(&app, &cw20_addr, 1));
+        }
+    }
+
     mod marketing {
         use super::*;
 
@@ -1716,7 +2699,7 @@ mod tests {
         }
 
         #[test]
-        fn update_marketing() {
+        fn update_marketing_admin() {
             let mut deps = mock_dependencies();
 
             let creator = deps.api.addr_make("creator");
@@ -1744,15 +2727,21 @@ mod tests {
                 deps.as_mut(),
                 mock_env(),
                 info,
-                ExecuteMsg::UpdateMarketing {
-                    project: None,
-                    description: None,
-                    marketing: Some(marketing.to_string()),
+                ExecuteMsg::UpdateMarketingAdmin {
+                    admin: Some(marketing.to_string()),
                 },
             )
             .unwrap();
 
             assert_eq!(res.messages, vec![]);
+            assert_eq!(
+                res.attributes,
+                vec![
+                    attr("action", "update_marketing_admin"),
+                    attr("old_admin", creator.as_str()),
+                    attr("new_admin", marketing.as_str()),
+                ]
+            );
 
             assert_eq!(
                 query_marketing_info(deps.as_ref()).unwrap(),
@@ -1771,6 +2760,57 @@ mod tests {
             );
         }
 
+        #[test]
+        fn update_marketing_ignores_admin() {
+            let mut deps = mock_dependencies();
+
+            let creator = deps.api.addr_make("creator");
+            let marketing = deps.api.addr_make("marketing");
+
+            let instantiate_msg = InstantiateMsg {
+                name: "Cash Token".to_string(),
+                symbol: "CASH".to_string(),
+                decimals: 9,
+                initial_balances: vec![],
+                mint: None,
+                marketing: Some(InstantiateMarketingInfo {
+                    project: Some("Project".to_owned()),
+                    description: Some("Description".to_owned()),
+                    marketing: Some(creator.to_string()),
+                    logo: Some(Logo::Url("url".to_owned())),
+                }),
+            };
+
+            let info = mock_info(creator.as_str(), &[]);
+
+            instantiate(deps.as_mut(), mock_env(), info.clone(), instantiate_msg).unwrap();
+
+            // the deprecated field neither moves nor clears the role
+            for admin in [marketing.to_string(), "".to_owned()] {
+                execute(
+                    deps.as_mut(),
+                    mock_env(),
+                    info.clone(),
+                    ExecuteMsg::UpdateMarketing {
+                        project: Some("New project".to_owned()),
+                        description: None,
+                        marketing: Some(admin),
+                    },
+                )
+                .unwrap();
+            }
+
+            assert_eq!(
+                query_marketing_info(deps.as_ref()).unwrap(),
+                MarketingInfoResponse {
+                    project: Some("New project".to_owned()),
+                    description: Some("Description".to_owned()),
+                    marketing: Some(creator),
+                    logo: Some(LogoInfo::Url("url".to_owned())),
+                }
+            );
+        }
+
         #[test]
         fn update_marketing_invalid() {
             let mut deps = mock_dependencies();
@@ -1799,10 +2839,8 @@ mod tests {
                 deps.as_mut(),
                 mock_env(),
                 info,
-                ExecuteMsg::UpdateMarketing {
-                    project: None,
-                    description: None,
-                    marketing: Some("m".to_owned()),
+                ExecuteMsg::UpdateMarketingAdmin {
+                    admin: Some("m".to_owned()),
                 },
             )
             .unwrap_err();
@@ -1830,7 +2868,7 @@ mod tests {
         }
 
         #[test]
-        fn clear_marketing() {
+        fn renounce_marketing_admin() {
             let mut deps = mock_dependencies();
 
             let creator = deps.api.addr_make("creator");
@@ -1856,16 +2894,20 @@ mod tests {
             let res = execute(
                 deps.as_mut(),
                 mock_env(),
-                info,
-                ExecuteMsg::UpdateMarketing {
-                    project: None,
-                    description: None,
-                    marketing: Some("".to_owned()),
-                },
+                info.clone(),
+                ExecuteMsg::UpdateMarketingAdmin { admin: None },
             )
             .unwrap();
 
             assert_eq!(res.messages, vec![]);
+            assert_eq!(
+                res.attributes,
+                vec![
+                    attr("action", "update_marketing_admin"),
+                    attr("old_admin", creator.as_str()),
+                    attr("new_admin", "None"),
+                ]
+            );
 
             assert_eq!(
                 query_marketing_info(deps.as_ref()).unwrap(),
@@ -1882,6 +2924,23 @@ mod tests {
                 matches!(err, StdError::NotFound { .. }),
                 "Expected StdError::NotFound, received {err}",
             );
+
+            // nothing can be changed anymore, not even by the former admin
+            let msgs = [
+                ExecuteMsg::UpdateMarketing {
+                    project: Some("New project".to_owned()),
+                    description: None,
+                    marketing: None,
+                },
+                ExecuteMsg::UploadLogo(Logo::Url("new_url".to_owned())),
+                ExecuteMsg::UpdateMarketingAdmin {
+                    admin: Some(creator.to_string()),
+                },
+            ];
+            for msg in msgs {
+                let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+                assert_eq!(err, ContractError::MarketingRenounced {});
+            }
         }
 
         #[test]
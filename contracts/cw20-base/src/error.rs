@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -23,6 +23,9 @@ pub enum ContractError {
     #[error("No allowance for this account")]
     NoAllowance {},
 
+    #[error("Current allowance is {current}, expected {expected}")]
+    AllowanceMismatch { expected: Uint128, current: Uint128 },
+
     #[error("Minting cannot exceed the cap")]
     CannotExceedCap {},
 
@@ -40,4 +43,10 @@ pub enum ContractError {
 
     #[error("Duplicate initial balance addresses")]
     DuplicateInitialBalanceAddresses {},
+
+    #[error("Marketing admin was renounced, marketing info can no longer be changed")]
+    MarketingRenounced {},
+
+    #[error("Unknown reply id: {id}")]
+    UnknownReplyId { id: u64 },
 }
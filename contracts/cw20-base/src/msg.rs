@@ -79,6 +79,9 @@ pub enum QueryMsg {
     /// Returns metadata on the contract - name, decimals, supply, etc.
     #[returns(cw20::TokenInfoResponse)]
     TokenInfo {},
+    /// Returns token info, minter, marketing info and contract version in one go.
+    #[returns(cw20::FullTokenInfoResponse)]
+    FullTokenInfo {},
     /// Only with "mintable" extension.
     /// Returns who can mint and the hard cap on maximum tokens after minting.
     #[returns(cw20::MinterResponse)]
@@ -20,6 +20,13 @@ pub struct MinterData {
     pub cap: Option<Uint128>,
 }
 
+#[cw_serde]
+pub struct SendLooseReply {
+    pub from: Addr,
+    pub to: Addr,
+    pub amount: Uint128,
+}
+
 impl TokenInfo {
     pub fn get_cap(&self) -> Option<Uint128> {
         self.mint.as_ref().and_then(|v| v.cap)
@@ -29,6 +36,14 @@ impl TokenInfo {
 pub const TOKEN_INFO: Item<TokenInfo> = Item::new("token_info");
 pub const MARKETING_INFO: Item<MarketingInfoResponse> = Item::new("marketing_info");
 pub const LOGO: Item<Logo> = Item::new("logo");
+/// Set once the marketing account renounced its role
+pub const MARKETING_RENOUNCED: Item<bool> = Item::new("marketing_renounced");
+/// Contract notified on every burn, set by the minter
+pub const BURN_HOOK: Item<Addr> = Item::new("burn_hook");
+/// Set by `SendLoose`, so the reply can describe a failed receive hook. Removed by the reply
+pub const SEND_LOOSE_REPLY: Map<u64, SendLooseReply> = Map::new("send_loose_reply");
+/// Number of `SendLoose` calls so far, used as the key of `SEND_LOOSE_REPLY`
+pub const SEND_LOOSE_COUNT: Item<u64> = Item::new("send_loose_count");
 pub const BALANCES: Map<&Addr, Uint128> = Map::new("balance");
 pub const ALLOWANCES: Map<(&Addr, &Addr), AllowanceResponse> = Map::new("allowance");
 // TODO: After https://github.com/CosmWasm/cw-plus/issues/670 is implemented, replace this with a `MultiIndex` over `ALLOWANCES`
@@ -1,8 +1,8 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    from_json, to_json_binary, Addr, Binary, Deps, DepsMut, Env, IbcMsg, IbcQuery, MessageInfo,
-    Order, PortIdResponse, Response, StdError, StdResult,
+    from_json, to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, IbcMsg, IbcQuery,
+    MessageInfo, Order, PortIdResponse, Response, StdError, StdResult, SubMsg,
 };
 use semver::Version;
 
@@ -12,15 +12,17 @@ use cw_storage_plus::Bound;
 
 use crate::amount::Amount;
 use crate::error::ContractError;
-use crate::ibc::Ics20Packet;
+use crate::ibc::{Ics20Packet, SEND_PACKET_ID};
 use crate::migrations::{v1, v2};
 use crate::msg::{
     AllowMsg, AllowedInfo, AllowedResponse, ChannelResponse, ConfigResponse, ExecuteMsg, InitMsg,
-    ListAllowedResponse, ListChannelsResponse, MigrateMsg, PortResponse, QueryMsg, TransferMsg,
+    ListAllowedResponse, ListChannelsResponse, ListForwardTargetsResponse, MigrateMsg,
+    PendingTransferInfo, PendingTransfersResponse, PortResponse, QueryMsg, TransferMsg,
 };
 use crate::state::{
-    increase_channel_balance, AllowInfo, Config, ADMIN, ALLOW_LIST, CHANNEL_INFO, CHANNEL_STATE,
-    CONFIG,
+    increase_channel_balance, AllowInfo, Config, PendingTransfer, SendReplyArgs, ADMIN, ALLOW_LIST,
+    CHANNEL_INFO, CHANNEL_STATE, CONFIG, DEFAULT_FORWARD_GAS_LIMIT, FORWARD_TARGETS,
+    PENDING_TRANSFERS,
 };
 use cw_utils::{maybe_addr, nonpayable, one_coin};
 
@@ -39,6 +41,7 @@ pub fn instantiate(
     let cfg = Config {
         default_timeout: msg.default_timeout,
         default_gas_limit: msg.default_gas_limit,
+        forward_gas_limit: msg.forward_gas_limit.unwrap_or(DEFAULT_FORWARD_GAS_LIMIT),
     };
     CONFIG.save(deps.storage, &cfg)?;
 
@@ -74,6 +77,9 @@ pub fn execute(
             let admin = deps.api.addr_validate(&admin)?;
             Ok(ADMIN.execute_update_admin(deps, info, Some(admin))?)
         }
+        ExecuteMsg::UpdateForwardTargets { add, remove } => {
+            execute_update_forward_targets(deps, info, add, remove)
+        }
     }
 }
 
@@ -144,16 +150,31 @@ pub fn execute_transfer(
     // This means the channel works fine if success acks are not relayed.
     increase_channel_balance(deps.storage, &msg.channel, &amount.denom(), amount.amount())?;
 
+    // the packet sequence is only known once it was sent, so the reply handler
+    // records the pending transfer
+    let reply_args = SendReplyArgs {
+        channel: msg.channel.clone(),
+        transfer: PendingTransfer {
+            sender: packet.sender.clone(),
+            receiver: packet.receiver.clone(),
+            denom: packet.denom.clone(),
+            amount: packet.amount,
+            timeout,
+        },
+    };
+
     // prepare ibc message
     let msg = IbcMsg::SendPacket {
         channel_id: msg.channel,
         data: to_json_binary(&packet)?,
         timeout: timeout.into(),
     };
+    let msg =
+        SubMsg::reply_on_success(msg, SEND_PACKET_ID).with_payload(to_json_binary(&reply_args)?);
 
     // send response
     let res = Response::new()
-        .add_message(msg)
+        .add_submessage(msg)
         .add_attribute("action", "transfer")
         .add_attribute("sender", &packet.sender)
         .add_attribute("receiver", &packet.receiver)
@@ -203,6 +224,31 @@ pub fn execute_allow(
     Ok(res)
 }
 
+/// The gov contract decides which contracts incoming transfers may be forwarded to.
+pub fn execute_update_forward_targets(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    for contract in &add {
+        let contract = deps.api.addr_validate(contract)?;
+        FORWARD_TARGETS.save(deps.storage, &contract, &Empty {})?;
+    }
+    for contract in &remove {
+        let contract = deps.api.addr_validate(contract)?;
+        FORWARD_TARGETS.remove(deps.storage, &contract);
+    }
+
+    let res = Response::new()
+        .add_attribute("action", "update_forward_targets")
+        .add_attribute("added", add.len().to_string())
+        .add_attribute("removed", remove.len().to_string());
+    Ok(res)
+}
+
 const MIGRATE_MIN_VERSION: &str = "0.11.1";
 const MIGRATE_VERSION_2: &str = "0.12.0-alpha1";
 // the new functionality starts in 0.13.1, this is the last release that needs to be migrated to v3
@@ -241,6 +287,7 @@ pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response,
         let config = Config {
             default_timeout: old_config.default_timeout,
             default_gas_limit: None,
+            forward_gas_limit: DEFAULT_FORWARD_GAS_LIMIT,
         };
         CONFIG.save(deps.storage, &config)?;
     }
@@ -258,6 +305,12 @@ pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response,
             Ok(old)
         })?;
     }
+    if let Some(forward_gas_limit) = msg.forward_gas_limit {
+        CONFIG.update(deps.storage, |mut old| -> StdResult<_> {
+            old.forward_gas_limit = forward_gas_limit;
+            Ok(old)
+        })?;
+    }
 
     // we don't need to save anything if migrating from the same version
     if storage_version < version {
@@ -283,6 +336,19 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             to_json_binary(&list_allowed(deps, start_after, limit)?)
         }
         QueryMsg::Admin {} => to_json_binary(&ADMIN.query_admin(deps)?),
+        QueryMsg::ListForwardTargets { start_after, limit } => {
+            to_json_binary(&list_forward_targets(deps, start_after, limit)?)
+        }
+        QueryMsg::PendingTransfers {
+            channel_id,
+            start_after,
+            limit,
+        } => to_json_binary(&list_pending_transfers(
+            deps,
+            channel_id,
+            start_after,
+            limit,
+        )?),
     }
 }
 
@@ -331,6 +397,7 @@ fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let res = ConfigResponse {
         default_timeout: cfg.default_timeout,
         default_gas_limit: cfg.default_gas_limit,
+        forward_gas_limit: cfg.forward_gas_limit,
         gov_contract: admin.into(),
     };
     Ok(res)
@@ -378,6 +445,50 @@ fn list_allowed(
     Ok(ListAllowedResponse { allow })
 }
 
+fn list_forward_targets(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ListForwardTargetsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let addr = maybe_addr(deps.api, start_after)?;
+    let start = addr.as_ref().map(Bound::exclusive);
+
+    let contracts = FORWARD_TARGETS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(Addr::into_string))
+        .collect::<StdResult<_>>()?;
+    Ok(ListForwardTargetsResponse { contracts })
+}
+
+fn list_pending_transfers(
+    deps: Deps,
+    channel_id: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<PendingTransfersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let transfers = PENDING_TRANSFERS
+        .prefix(&channel_id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(sequence, transfer)| PendingTransferInfo {
+                sequence,
+                sender: transfer.sender,
+                receiver: transfer.receiver,
+                denom: transfer.denom,
+                amount: transfer.amount,
+                timeout: transfer.timeout,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+    Ok(PendingTransfersResponse { transfers })
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -571,6 +682,7 @@ mod test {
             mock_env(),
             MigrateMsg {
                 default_gas_limit: Some(123456),
+                forward_gas_limit: None,
             },
         )
         .unwrap();
@@ -611,6 +723,7 @@ mod test {
             mock_env(),
             MigrateMsg {
                 default_gas_limit: Some(123456),
+                forward_gas_limit: Some(200_000),
             },
         )
         .unwrap();
@@ -623,6 +736,7 @@ mod test {
         // check config updates
         let config = query_config(deps.as_ref()).unwrap();
         assert_eq!(config.default_gas_limit, Some(123456));
+        assert_eq!(config.forward_gas_limit, 200_000);
     }
 
     fn test_with_memo(memo: &str) {
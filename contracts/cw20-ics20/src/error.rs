@@ -65,6 +65,12 @@ pub enum ContractError {
 
     #[error("You can only send cw20 tokens that have been explicitly allowed by governance")]
     NotOnAllowList,
+
+    #[error("Contract {contract} is not allowed as a forward target")]
+    ForwardNotAllowed { contract: String },
+
+    #[error("Forward message is {size} bytes, the limit is {max}")]
+    ForwardMsgTooLarge { size: usize, max: usize },
 }
 
 impl From<FromUtf8Error> for ContractError {
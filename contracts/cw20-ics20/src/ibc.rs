@@ -5,7 +5,7 @@ use cosmwasm_schema::cw_serde;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    attr, from_json, to_json_binary, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env,
+    attr, from_json, to_json_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, Event,
     Ibc3ChannelOpenResponse, IbcBasicResponse, IbcChannel, IbcChannelCloseMsg,
     IbcChannelConnectMsg, IbcChannelOpenMsg, IbcEndpoint, IbcOrder, IbcPacket, IbcPacketAckMsg,
     IbcPacketReceiveMsg, IbcPacketTimeoutMsg, IbcReceiveResponse, Reply, Response, SubMsg,
@@ -15,8 +15,9 @@ use cosmwasm_std::{
 use crate::amount::Amount;
 use crate::error::{ContractError, Never};
 use crate::state::{
-    reduce_channel_balance, undo_reduce_channel_balance, ChannelInfo, ReplyArgs, ALLOW_LIST,
-    CHANNEL_INFO, CONFIG, REPLY_ARGS,
+    reduce_channel_balance, undo_reduce_channel_balance, ChannelInfo, ForwardReplyArgs, ReplyArgs,
+    SendReplyArgs, ALLOW_LIST, CHANNEL_INFO, CONFIG, FORWARD_TARGETS, PENDING_TRANSFERS,
+    REPLY_ARGS,
 };
 use cw20::Cw20ExecuteMsg;
 
@@ -65,6 +66,27 @@ impl Ics20Packet {
     }
 }
 
+/// Memo of an incoming transfer that asks to forward the tokens to a contract instead of
+/// crediting the receiver, eg. `{"forward":{"contract":"wasm1...","msg":"eyJzdGFrZSI6e319"}}`.
+/// Native tokens are sent along with `msg` executed on the contract, cw20 tokens are
+/// passed on with `Cw20ExecuteMsg::Send`. If the forward is not possible, the receiver
+/// is credited as usual.
+#[cw_serde]
+pub struct ForwardMemo {
+    pub forward: ForwardMsg,
+}
+
+#[cw_serde]
+pub struct ForwardMsg {
+    /// Must be on the list of forward targets set by governance
+    pub contract: String,
+    /// At most MAX_FORWARD_MSG_SIZE bytes
+    pub msg: Binary,
+}
+
+/// Upper limit for the message in a forward memo
+pub const MAX_FORWARD_MSG_SIZE: usize = 2048;
+
 /// This is a generic ICS acknowledgement format.
 /// Proto defined here: https://github.com/cosmos/cosmos-sdk/blob/v0.42.0/proto/ibc/core/channel/v1/channel.proto#L141-L147
 /// This is compatible with the JSON serialization
@@ -88,6 +110,10 @@ fn ack_fail(err: String) -> Binary {
 
 const RECEIVE_ID: u64 = 1337;
 const ACK_FAILURE_ID: u64 = 0xfa17;
+pub(crate) const SEND_PACKET_ID: u64 = 0x5e4d;
+/// Type of the response wasmd returns for `IbcMsg::SendPacket`
+const IBC_SEND_RESPONSE_TYPE: &str = "/cosmwasm.wasm.v1.MsgIBCSendResponse";
+const FORWARD_ID: u64 = 0xf0a2;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, _env: Env, reply: Reply) -> Result<Response, ContractError> {
@@ -122,10 +148,74 @@ pub fn reply(deps: DepsMut, _env: Env, reply: Reply) -> Result<Response, Contrac
             SubMsgResult::Ok(_) => Ok(Response::new()),
             SubMsgResult::Err(err) => Ok(Response::new().set_data(ack_fail(err))),
         },
+        FORWARD_ID => match reply.result {
+            SubMsgResult::Ok(_) => {
+                // only now we know the target accepted the tokens
+                let args: ForwardReplyArgs = from_json(&reply.payload)?;
+                Ok(Response::new().add_event(forward_event(&args.contract, "forwarded")))
+            }
+            SubMsgResult::Err(err) => {
+                // The forward target failed, so we credit the receiver like a plain transfer.
+                // The receive state is still in REPLY_ARGS, and if this fails as well it is
+                // handled (and reverted) just like any other failed receive
+                let args: ForwardReplyArgs = from_json(&reply.payload)?;
+                let reply_args = REPLY_ARGS.load(deps.storage)?;
+                let to_send = Amount::from_parts(reply_args.denom, reply_args.amount);
+                let gas_limit = check_gas_limit(deps.as_ref(), &to_send)?;
+                let mut submsg =
+                    SubMsg::reply_on_error(send_amount(to_send, args.receiver), RECEIVE_ID);
+                submsg.gas_limit = gas_limit;
+
+                Ok(Response::new()
+                    .add_submessage(submsg)
+                    .add_event(forward_event(&args.contract, "failed").add_attribute("error", err)))
+            }
+        },
+        SEND_PACKET_ID => {
+            let SubMsgResult::Ok(res) = reply.result else {
+                // only registered as reply_on_success
+                return Ok(Response::new());
+            };
+            // The transfer itself is fine even if we cannot tell the sequence,
+            // so this just means it won't show up as pending
+            #[allow(deprecated)]
+            let data = if res.msg_responses.is_empty() {
+                res.data.as_ref()
+            } else {
+                res.msg_responses
+                    .iter()
+                    .find(|msg_response| msg_response.type_url == IBC_SEND_RESPONSE_TYPE)
+                    .map(|msg_response| &msg_response.value)
+            };
+            if let Some(sequence) = data.and_then(|data| parse_send_sequence(data)) {
+                let args: SendReplyArgs = from_json(&reply.payload)?;
+                PENDING_TRANSFERS.save(deps.storage, (&args.channel, sequence), &args.transfer)?;
+            }
+            Ok(Response::new())
+        }
         _ => Err(ContractError::UnknownReplyId { id: reply.id }),
     }
 }
 
+/// Reads the sequence out of a `MsgIBCSendResponse`, which is the protobuf message
+/// `{ uint64 sequence = 1; }`
+fn parse_send_sequence(data: &[u8]) -> Option<u64> {
+    let (&tag, mut rest) = data.split_first()?;
+    if tag != 0x08 {
+        return None;
+    }
+    let mut sequence = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, tail) = rest.split_first()?;
+        sequence |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(sequence);
+        }
+        rest = tail;
+    }
+    None
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 /// enforces ordering and versioning constraints
 pub fn ibc_channel_open(
@@ -187,7 +277,8 @@ pub fn ibc_channel_close(
     _channel: IbcChannelCloseMsg,
 ) -> Result<IbcBasicResponse, ContractError> {
     // TODO: what to do here?
-    // we will have locked funds that need to be returned somehow
+    // we will have locked funds that need to be returned somehow,
+    // and PENDING_TRANSFERS of the channel would need to be cleared
     unimplemented!();
 }
 
@@ -262,11 +353,48 @@ fn do_ibc_packet_receive(
 
     let to_send = Amount::from_parts(denom.to_string(), msg.amount);
     let gas_limit = check_gas_limit(deps.as_ref(), &to_send)?;
-    let send = send_amount(to_send, msg.receiver.clone());
-    let mut submsg = SubMsg::reply_on_error(send, RECEIVE_ID);
-    submsg.gas_limit = gas_limit;
+    let mut res = IbcReceiveResponse::new(ack_success());
+
+    // memos that are not meant for us are fine, we just credit the receiver then
+    let forward = msg
+        .memo
+        .as_deref()
+        .and_then(|memo| from_json::<ForwardMemo>(memo).ok());
+    let mut submsg = match forward.map(|memo| memo.forward) {
+        Some(forward) => match check_forward(deps.as_ref(), &forward) {
+            Ok(contract) => {
+                let args = ForwardReplyArgs {
+                    receiver: msg.receiver.clone(),
+                    contract: forward.contract,
+                };
+                let send = forward_amount(to_send, contract.into_string(), forward.msg);
+                // limited, so the target cannot use up the gas the fallback in reply needs
+                let forward_gas_limit = CONFIG.load(deps.storage)?.forward_gas_limit;
+                SubMsg::reply_always(send, FORWARD_ID)
+                    .with_payload(to_json_binary(&args)?)
+                    .with_gas_limit(forward_gas_limit)
+            }
+            Err(err) => {
+                res = res.add_event(
+                    forward_event(&forward.contract, "rejected")
+                        .add_attribute("error", err.to_string()),
+                );
+                let send = send_amount(to_send, msg.receiver.clone());
+                SubMsg::reply_on_error(send, RECEIVE_ID)
+            }
+        },
+        None => {
+            let send = send_amount(to_send, msg.receiver.clone());
+            SubMsg::reply_on_error(send, RECEIVE_ID)
+        }
+    };
+    // a forward is bound by both the cw20 and the forward gas limit
+    submsg.gas_limit = match (submsg.gas_limit, gas_limit) {
+        (Some(forward), Some(cw20)) => Some(forward.min(cw20)),
+        (forward, cw20) => forward.or(cw20),
+    };
 
-    let res = IbcReceiveResponse::new(ack_success())
+    let res = res
         .add_submessage(submsg)
         .add_attribute("action", "receive")
         .add_attribute("sender", msg.sender)
@@ -278,6 +406,28 @@ fn do_ibc_packet_receive(
     Ok(res)
 }
 
+fn check_forward(deps: Deps, forward: &ForwardMsg) -> Result<Addr, ContractError> {
+    if forward.msg.len() > MAX_FORWARD_MSG_SIZE {
+        return Err(ContractError::ForwardMsgTooLarge {
+            size: forward.msg.len(),
+            max: MAX_FORWARD_MSG_SIZE,
+        });
+    }
+    let contract = deps.api.addr_validate(&forward.contract)?;
+    if !FORWARD_TARGETS.has(deps.storage, &contract) {
+        return Err(ContractError::ForwardNotAllowed {
+            contract: forward.contract.clone(),
+        });
+    }
+    Ok(contract)
+}
+
+fn forward_event(contract: &str, result: &str) -> Event {
+    Event::new("ics20_forward")
+        .add_attribute("contract", contract)
+        .add_attribute("result", result)
+}
+
 fn check_gas_limit(deps: Deps, amount: &Amount) -> Result<Option<u64>, ContractError> {
     match amount {
         Amount::Cw20(coin) => {
@@ -326,8 +476,9 @@ pub fn ibc_packet_timeout(
 }
 
 // update the balance stored on this (channel, denom) index
-fn on_packet_success(_deps: DepsMut, packet: IbcPacket) -> Result<IbcBasicResponse, ContractError> {
+fn on_packet_success(deps: DepsMut, packet: IbcPacket) -> Result<IbcBasicResponse, ContractError> {
     let msg: Ics20Packet = from_json(packet.data)?;
+    PENDING_TRANSFERS.remove(deps.storage, (&packet.src.channel_id, packet.sequence));
 
     // similar event messages like ibctransfer module
     let attributes = vec![
@@ -349,6 +500,7 @@ fn on_packet_failure(
     err: String,
 ) -> Result<IbcBasicResponse, ContractError> {
     let msg: Ics20Packet = from_json(&packet.data)?;
+    PENDING_TRANSFERS.remove(deps.storage, (&packet.src.channel_id, packet.sequence));
 
     // undo the balance update on failure (as we pre-emptively added it on send)
     reduce_channel_balance(deps.storage, &packet.src.channel_id, &msg.denom, msg.amount)?;
@@ -395,16 +547,48 @@ fn send_amount(amount: Amount, recipient: String) -> CosmosMsg {
     }
 }
 
+fn forward_amount(amount: Amount, contract: String, msg: Binary) -> CosmosMsg {
+    match amount {
+        Amount::Native(coin) => WasmMsg::Execute {
+            contract_addr: contract,
+            msg,
+            funds: vec![coin],
+        }
+        .into(),
+        Amount::Cw20(coin) => {
+            let msg = Cw20ExecuteMsg::Send {
+                contract,
+                amount: coin.amount,
+                msg,
+            };
+            WasmMsg::Execute {
+                contract_addr: coin.address,
+                msg: to_json_binary(&msg).unwrap(),
+                funds: vec![],
+            }
+            .into()
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::test_helpers::*;
 
-    use crate::contract::{execute, migrate, query_channel};
-    use crate::msg::{ExecuteMsg, MigrateMsg, TransferMsg};
+    use crate::contract::{execute, migrate, query, query_channel};
+    use crate::msg::{
+        ExecuteMsg, ListForwardTargetsResponse, MigrateMsg, PendingTransferInfo,
+        PendingTransfersResponse, QueryMsg, TransferMsg,
+    };
+    use crate::state::{PendingTransfer, DEFAULT_FORWARD_GAS_LIMIT};
     use cosmwasm_std::testing::{mock_env, mock_info};
-    use cosmwasm_std::{coins, to_json_vec, Addr, IbcEndpoint, IbcMsg, IbcTimeout, Timestamp};
+    use cosmwasm_std::{
+        coins, to_json_vec, Addr, IbcAcknowledgement, IbcMsg, IbcTimeout, MsgResponse,
+        SubMsgResponse, Timestamp,
+    };
     use cw20::Cw20ReceiveMsg;
+    use cw_controllers::AdminError;
 
     use easy_addr::addr;
 
@@ -546,13 +730,27 @@ mod test {
         };
         let timeout = mock_env().block.time.plus_seconds(DEFAULT_TIMEOUT);
 
+        let reply_args = SendReplyArgs {
+            channel: send_channel.to_string(),
+            transfer: PendingTransfer {
+                sender: local_sender.to_string(),
+                receiver: remote_rcpt.to_string(),
+                denom: cw20_denom.into(),
+                amount: Uint128::new(987654321),
+                timeout,
+            },
+        };
         assert_eq!(
             &res.messages[0],
-            &SubMsg::new(IbcMsg::SendPacket {
-                channel_id: send_channel.to_string(),
-                data: to_json_binary(&expected).unwrap(),
-                timeout: IbcTimeout::with_timestamp(timeout),
-            })
+            &SubMsg::reply_on_success(
+                IbcMsg::SendPacket {
+                    channel_id: send_channel.to_string(),
+                    data: to_json_binary(&expected).unwrap(),
+                    timeout: IbcTimeout::with_timestamp(timeout),
+                },
+                SEND_PACKET_ID
+            )
+            .with_payload(to_json_binary(&reply_args).unwrap())
         );
 
         // query channel state|_|
@@ -646,6 +844,429 @@ mod test {
         assert_eq!(state.total_sent, vec![Amount::native(987654321, denom)]);
     }
 
+    fn mock_send_reply(msg: &SubMsg, sequence: u8) -> Reply {
+        mock_msg_response_reply(msg, IBC_SEND_RESPONSE_TYPE, sequence)
+    }
+
+    fn mock_msg_response_reply(msg: &SubMsg, type_url: &str, sequence: u8) -> Reply {
+        #[allow(deprecated)]
+        let response = SubMsgResponse {
+            events: vec![],
+            data: None,
+            msg_responses: vec![MsgResponse {
+                type_url: type_url.to_string(),
+                value: Binary::from([0x08, sequence]),
+            }],
+        };
+        Reply {
+            id: msg.id,
+            payload: msg.payload.clone(),
+            gas_used: 0,
+            result: SubMsgResult::Ok(response),
+        }
+    }
+
+    fn query_pending(
+        deps: Deps,
+        channel_id: &str,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> Vec<u64> {
+        let msg = QueryMsg::PendingTransfers {
+            channel_id: channel_id.to_string(),
+            start_after,
+            limit,
+        };
+        let res: PendingTransfersResponse =
+            from_json(query(deps, mock_env(), msg).unwrap()).unwrap();
+        res.transfers.into_iter().map(|t| t.sequence).collect()
+    }
+
+    #[test]
+    fn pending_transfers_tracked_until_ack() {
+        let send_channel = "channel-9";
+        let mut deps = setup(&["channel-1", send_channel], &[]);
+        let denom = "uatom";
+
+        // send three transfers, sequences 3, 4, 5
+        let mut packets = vec![];
+        for sequence in 3..6 {
+            let msg = ExecuteMsg::Transfer(TransferMsg {
+                channel: send_channel.to_string(),
+                remote_address: "my-remote-address".to_string(),
+                timeout: None,
+                memo: None,
+            });
+            let info = mock_info("local-sender", &coins(1000, denom));
+            let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+            assert_eq!(res.messages[0].id, SEND_PACKET_ID);
+
+            // nothing is pending until we know the sequence
+            let pending = query_pending(deps.as_ref(), send_channel, None, None);
+            assert_eq!(pending.len(), sequence as usize - 3);
+
+            let reply_msg = mock_send_reply(&res.messages[0], sequence);
+            reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+
+            let data = match &res.messages[0].msg {
+                CosmosMsg::Ibc(IbcMsg::SendPacket { data, .. }) => data.clone(),
+                msg => panic!("unexpected message: {:?}", msg),
+            };
+            packets.push(IbcPacket::new(
+                data,
+                IbcEndpoint {
+                    port_id: CONTRACT_PORT.to_string(),
+                    channel_id: send_channel.to_string(),
+                },
+                IbcEndpoint {
+                    port_id: REMOTE_PORT.to_string(),
+                    channel_id: "channel-1234".to_string(),
+                },
+                sequence.into(),
+                mock_env().block.time.plus_seconds(DEFAULT_TIMEOUT).into(),
+            ));
+        }
+
+        // all listed with details, paginated by sequence
+        let msg = QueryMsg::PendingTransfers {
+            channel_id: send_channel.to_string(),
+            start_after: None,
+            limit: Some(1),
+        };
+        let res: PendingTransfersResponse =
+            from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(
+            res.transfers,
+            vec![PendingTransferInfo {
+                sequence: 3,
+                sender: "local-sender".to_string(),
+                receiver: "my-remote-address".to_string(),
+                denom: denom.to_string(),
+                amount: Uint128::new(1000),
+                timeout: mock_env().block.time.plus_seconds(DEFAULT_TIMEOUT),
+            }]
+        );
+        assert_eq!(
+            query_pending(deps.as_ref(), send_channel, Some(3), Some(1)),
+            vec![4]
+        );
+        assert_eq!(
+            query_pending(deps.as_ref(), send_channel, Some(4), None),
+            vec![5]
+        );
+        // other channels have nothing pending
+        assert!(query_pending(deps.as_ref(), "channel-1", None, None).is_empty());
+
+        // successful ack clears the transfer
+        let ack = IbcAcknowledgement::new(ack_success());
+        let msg = IbcPacketAckMsg::new(ack, packets[1].clone(), Addr::unchecked(""));
+        ibc_packet_ack(deps.as_mut(), mock_env(), msg).unwrap();
+        assert_eq!(
+            query_pending(deps.as_ref(), send_channel, None, None),
+            vec![3, 5]
+        );
+
+        // so does a failed ack
+        let ack = IbcAcknowledgement::new(ack_fail("oops".to_string()));
+        let msg = IbcPacketAckMsg::new(ack, packets[0].clone(), Addr::unchecked(""));
+        ibc_packet_ack(deps.as_mut(), mock_env(), msg).unwrap();
+        assert_eq!(
+            query_pending(deps.as_ref(), send_channel, None, None),
+            vec![5]
+        );
+
+        // and a timeout
+        let msg = IbcPacketTimeoutMsg::new(packets[2].clone(), Addr::unchecked(""));
+        ibc_packet_timeout(deps.as_mut(), mock_env(), msg).unwrap();
+        assert!(query_pending(deps.as_ref(), send_channel, None, None).is_empty());
+    }
+
+    #[test]
+    fn pending_transfer_needs_send_response() {
+        let send_channel = "channel-9";
+        let mut deps = setup(&[send_channel], &[]);
+
+        let msg = ExecuteMsg::Transfer(TransferMsg {
+            channel: send_channel.to_string(),
+            remote_address: "my-remote-address".to_string(),
+            timeout: None,
+            memo: None,
+        });
+        let info = mock_info("local-sender", &coins(1000, "uatom"));
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // some other response is not read as the sequence
+        let reply_msg = mock_msg_response_reply(
+            &res.messages[0],
+            "/ibc.applications.transfer.v1.MsgTransferResponse",
+            7,
+        );
+        reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+        assert!(query_pending(deps.as_ref(), send_channel, None, None).is_empty());
+
+        let reply_msg = mock_send_reply(&res.messages[0], 7);
+        reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+        assert_eq!(
+            query_pending(deps.as_ref(), send_channel, None, None),
+            vec![7]
+        );
+    }
+
+    #[test]
+    fn parse_send_sequence_varint() {
+        assert_eq!(parse_send_sequence(&[0x08, 0x01]), Some(1));
+        assert_eq!(parse_send_sequence(&[0x08, 0xac, 0x02]), Some(300));
+        assert_eq!(
+            parse_send_sequence(&[0x08, 0xff, 0xff, 0xff, 0xff, 0x0f]),
+            Some(u32::MAX.into())
+        );
+        // wrong field, truncated or empty data
+        assert_eq!(parse_send_sequence(&[0x10, 0x01]), None);
+        assert_eq!(parse_send_sequence(&[0x08, 0x80]), None);
+        assert_eq!(parse_send_sequence(&[]), None);
+    }
+
+    fn with_memo(packet: IbcPacket, memo: &str) -> IbcPacket {
+        let data: Ics20Packet = from_json(&packet.data).unwrap();
+        let data = data.with_memo(Some(memo.to_string()));
+        IbcPacket::new(
+            to_json_binary(&data).unwrap(),
+            packet.src,
+            packet.dest,
+            packet.sequence,
+            packet.timeout,
+        )
+    }
+
+    fn forward_memo(contract: &str, msg: &[u8]) -> String {
+        let memo = ForwardMemo {
+            forward: ForwardMsg {
+                contract: contract.to_string(),
+                msg: msg.into(),
+            },
+        };
+        String::from_utf8(to_json_vec(&memo).unwrap()).unwrap()
+    }
+
+    fn update_forward_targets(deps: DepsMut, add: &[&str], remove: &[&str]) {
+        let msg = ExecuteMsg::UpdateForwardTargets {
+            add: add.iter().map(|a| a.to_string()).collect(),
+            remove: remove.iter().map(|a| a.to_string()).collect(),
+        };
+        let info = mock_info(addr!("gov"), &[]);
+        execute(deps, mock_env(), info, msg).unwrap();
+    }
+
+    #[test]
+    fn forward_targets_managed_by_gov() {
+        let mut deps = setup(&["channel-1"], &[]);
+        let target = addr!("target");
+        let other = addr!("other");
+
+        let msg = ExecuteMsg::UpdateForwardTargets {
+            add: vec![target.to_string()],
+            remove: vec![],
+        };
+        let info = mock_info(addr!("anyone"), &[]);
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(err, ContractError::Admin(AdminError::NotAdmin {}));
+
+        update_forward_targets(deps.as_mut(), &[target, other], &[]);
+        update_forward_targets(deps.as_mut(), &[], &[other]);
+
+        let msg = QueryMsg::ListForwardTargets {
+            start_after: None,
+            limit: None,
+        };
+        let res: ListForwardTargetsResponse =
+            from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(res.contracts, vec![target.to_string()]);
+    }
+
+    #[test]
+    fn forward_native_to_contract() {
+        let send_channel = "channel-9";
+        let mut deps = setup(&[send_channel], &[]);
+        let denom = "uatom";
+        let target = addr!("staking");
+        let local_rcpt = addr!("local-rcpt");
+        update_forward_targets(deps.as_mut(), &[target], &[]);
+
+        // we need something on the channel first
+        let msg = ExecuteMsg::Transfer(TransferMsg {
+            channel: send_channel.to_string(),
+            remote_address: "my-remote-address".to_string(),
+            timeout: None,
+            memo: None,
+        });
+        let info = mock_info("local-sender", &coins(1000, denom));
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let packet = mock_receive_packet(send_channel, 600, denom, local_rcpt);
+        let packet = with_memo(packet, &forward_memo(target, b"{\"stake\":{}}"));
+        let msg = IbcPacketReceiveMsg::new(packet, Addr::unchecked(""));
+        let res = ibc_packet_receive(deps.as_mut(), mock_env(), msg).unwrap();
+        let ack: Ics20Ack = from_json(res.acknowledgement.unwrap()).unwrap();
+        assert!(matches!(ack, Ics20Ack::Result(_)));
+
+        let args = ForwardReplyArgs {
+            receiver: local_rcpt.to_string(),
+            contract: target.to_string(),
+        };
+        let forward = SubMsg::reply_always(
+            WasmMsg::Execute {
+                contract_addr: target.to_string(),
+                msg: b"{\"stake\":{}}".into(),
+                funds: coins(600, denom),
+            },
+            FORWARD_ID,
+        )
+        .with_payload(to_json_binary(&args).unwrap())
+        .with_gas_limit(DEFAULT_FORWARD_GAS_LIMIT);
+        assert_eq!(res.messages, vec![forward.clone()]);
+        // the outcome is only known in the reply
+        assert!(res.events.is_empty());
+
+        // the target accepts the tokens
+        #[allow(deprecated)]
+        let reply_msg = Reply {
+            id: FORWARD_ID,
+            payload: forward.payload.clone(),
+            gas_used: 0,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+                msg_responses: vec![],
+            }),
+        };
+        let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+        assert!(res.messages.is_empty());
+        assert_eq!(res.events, vec![forward_event(target, "forwarded")]);
+
+        // the target fails, so the receiver gets the tokens
+        let reply_msg = Reply {
+            id: FORWARD_ID,
+            payload: forward.payload,
+            gas_used: 0,
+            result: SubMsgResult::Err("out of gas".to_string()),
+        };
+        let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+        assert_eq!(res.messages, vec![native_payment(600, denom, local_rcpt)]);
+        assert_eq!(
+            res.events,
+            vec![forward_event(target, "failed").add_attribute("error", "out of gas")]
+        );
+        assert_eq!(res.data, None);
+
+        // channel balance is reduced either way
+        let state = query_channel(deps.as_ref(), send_channel.to_string()).unwrap();
+        assert_eq!(state.balances, vec![Amount::native(400, denom)]);
+    }
+
+    #[test]
+    fn forward_cw20_to_contract() {
+        let send_channel = "channel-9";
+        let cw20_addr = addr!("token-addr");
+        let cw20_denom = concat!("cw20:", addr!("token-addr"));
+        // below the forward gas limit, so it is the one that applies
+        let gas_limit = 123456;
+        let mut deps = setup(&[send_channel], &[(cw20_addr, gas_limit)]);
+        let target = addr!("staking");
+        update_forward_targets(deps.as_mut(), &[target], &[]);
+
+        let transfer = TransferMsg {
+            channel: send_channel.to_string(),
+            remote_address: "my-remote-address".to_string(),
+            timeout: None,
+            memo: None,
+        };
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: addr!("local-sender").to_string(),
+            amount: Uint128::new(1000),
+            msg: to_json_binary(&transfer).unwrap(),
+        });
+        execute(deps.as_mut(), mock_env(), mock_info(cw20_addr, &[]), msg).unwrap();
+
+        let packet = mock_receive_packet(send_channel, 600, cw20_denom, addr!("local-rcpt"));
+        let packet = with_memo(packet, &forward_memo(target, b"{}"));
+        let msg = IbcPacketReceiveMsg::new(packet, Addr::unchecked(""));
+        let res = ibc_packet_receive(deps.as_mut(), mock_env(), msg).unwrap();
+
+        let send = Cw20ExecuteMsg::Send {
+            contract: target.to_string(),
+            amount: Uint128::new(600),
+            msg: b"{}".into(),
+        };
+        assert_eq!(1, res.messages.len());
+        assert_eq!(res.messages[0].id, FORWARD_ID);
+        assert_eq!(res.messages[0].gas_limit, Some(gas_limit));
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: cw20_addr.to_string(),
+                msg: to_json_binary(&send).unwrap(),
+                funds: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn rejected_forward_credits_receiver() {
+        let send_channel = "channel-9";
+        let mut deps = setup(&[send_channel], &[]);
+        let denom = "uatom";
+        let target = addr!("staking");
+        let local_rcpt = addr!("local-rcpt");
+
+        let msg = ExecuteMsg::Transfer(TransferMsg {
+            channel: send_channel.to_string(),
+            remote_address: "my-remote-address".to_string(),
+            timeout: None,
+            memo: None,
+        });
+        let info = mock_info("local-sender", &coins(1000, denom));
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // target not allowed
+        let packet = mock_receive_packet(send_channel, 100, denom, local_rcpt);
+        let packet = with_memo(packet, &forward_memo(target, b"{}"));
+        let msg = IbcPacketReceiveMsg::new(packet, Addr::unchecked(""));
+        let res = ibc_packet_receive(deps.as_mut(), mock_env(), msg).unwrap();
+        assert_eq!(res.messages, vec![native_payment(100, denom, local_rcpt)]);
+        let err = ContractError::ForwardNotAllowed {
+            contract: target.to_string(),
+        };
+        assert_eq!(
+            res.events,
+            vec![forward_event(target, "rejected").add_attribute("error", err.to_string())]
+        );
+
+        // message too large
+        update_forward_targets(deps.as_mut(), &[target], &[]);
+        let big_msg = vec![b' '; MAX_FORWARD_MSG_SIZE + 1];
+        let packet = mock_receive_packet(send_channel, 100, denom, local_rcpt);
+        let packet = with_memo(packet, &forward_memo(target, &big_msg));
+        let msg = IbcPacketReceiveMsg::new(packet, Addr::unchecked(""));
+        let res = ibc_packet_receive(deps.as_mut(), mock_env(), msg).unwrap();
+        assert_eq!(res.messages, vec![native_payment(100, denom, local_rcpt)]);
+        let err = ContractError::ForwardMsgTooLarge {
+            size: MAX_FORWARD_MSG_SIZE + 1,
+            max: MAX_FORWARD_MSG_SIZE,
+        };
+        assert_eq!(
+            res.events,
+            vec![forward_event(target, "rejected").add_attribute("error", err.to_string())]
+        );
+
+        // other memos are ignored
+        let packet = mock_receive_packet(send_channel, 100, denom, local_rcpt);
+        let packet = with_memo(packet, "thanks for the fish");
+        let msg = IbcPacketReceiveMsg::new(packet, Addr::unchecked(""));
+        let res = ibc_packet_receive(deps.as_mut(), mock_env(), msg).unwrap();
+        assert_eq!(res.messages, vec![native_payment(100, denom, local_rcpt)]);
+        assert!(res.events.is_empty());
+    }
+
     #[test]
     fn check_gas_limit_handles_all_cases() {
         let send_channel = "channel-9";
@@ -668,6 +1289,7 @@ mod test {
             mock_env(),
             MigrateMsg {
                 default_gas_limit: Some(def_limit),
+                forward_gas_limit: None,
             },
         )
         .unwrap();
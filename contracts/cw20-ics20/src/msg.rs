@@ -1,6 +1,8 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cw20::Cw20ReceiveMsg;
 
+use cosmwasm_std::{Timestamp, Uint128};
+
 use crate::amount::Amount;
 use crate::state::ChannelInfo;
 
@@ -15,6 +17,10 @@ pub struct InitMsg {
     /// If set, contracts off the allowlist will run with this gas limit.
     /// If unset, will refuse to accept any contract off the allow list.
     pub default_gas_limit: Option<u64>,
+    /// Gas limit for forwarding incoming transfers to a contract, so the receiver is still
+    /// credited if the target runs out of gas. Defaults to `DEFAULT_FORWARD_GAS_LIMIT`.
+    #[serde(default)]
+    pub forward_gas_limit: Option<u64>,
 }
 
 #[cw_serde]
@@ -26,6 +32,8 @@ pub struct AllowMsg {
 #[cw_serde]
 pub struct MigrateMsg {
     pub default_gas_limit: Option<u64>,
+    #[serde(default)]
+    pub forward_gas_limit: Option<u64>,
 }
 
 #[cw_serde]
@@ -38,6 +46,12 @@ pub enum ExecuteMsg {
     Allow(AllowMsg),
     /// Change the admin (must be called by current admin)
     UpdateAdmin { admin: String },
+    /// This must be called by gov_contract, changes which contracts incoming transfers
+    /// may be forwarded to via the packet memo
+    UpdateForwardTargets {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
 }
 
 /// This is the message we accept via Receive
@@ -81,6 +95,20 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// List all contracts incoming transfers may be forwarded to.
+    #[returns(ListForwardTargetsResponse)]
+    ListForwardTargets {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// List the transfers sent over the given channel that are still waiting
+    /// for an acknowledgement or timeout, ordered by packet sequence.
+    #[returns(PendingTransfersResponse)]
+    PendingTransfers {
+        channel_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -108,6 +136,7 @@ pub struct PortResponse {
 pub struct ConfigResponse {
     pub default_timeout: u64,
     pub default_gas_limit: Option<u64>,
+    pub forward_gas_limit: u64,
     pub gov_contract: String,
 }
 
@@ -127,3 +156,23 @@ pub struct AllowedInfo {
     pub contract: String,
     pub gas_limit: Option<u64>,
 }
+
+#[cw_serde]
+pub struct PendingTransfersResponse {
+    pub transfers: Vec<PendingTransferInfo>,
+}
+
+#[cw_serde]
+pub struct PendingTransferInfo {
+    pub sequence: u64,
+    pub sender: String,
+    pub receiver: String,
+    pub denom: String,
+    pub amount: Uint128,
+    pub timeout: Timestamp,
+}
+
+#[cw_serde]
+pub struct ListForwardTargetsResponse {
+    pub contracts: Vec<String>,
+}
@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, IbcEndpoint, StdResult, Storage, Uint128};
+use cosmwasm_std::{Addr, Empty, IbcEndpoint, StdResult, Storage, Timestamp, Uint128};
 use cw_controllers::Admin;
 use cw_storage_plus::{Item, Map};
 
@@ -21,6 +21,12 @@ pub const CHANNEL_STATE: Map<(&str, &str), ChannelState> = Map::new("channel_sta
 /// Every cw20 contract we allow to be sent is stored here, possibly with a gas_limit
 pub const ALLOW_LIST: Map<&Addr, AllowInfo> = Map::new("allow_list");
 
+/// Contracts that incoming transfers may be forwarded to, managed by governance
+pub const FORWARD_TARGETS: Map<&Addr, Empty> = Map::new("forward_targets");
+
+/// indexed by (channel_id, sequence), every packet we sent that was not acknowledged nor timed out yet
+pub const PENDING_TRANSFERS: Map<(&str, u64), PendingTransfer> = Map::new("pending_transfers");
+
 #[cw_serde]
 #[derive(Default)]
 pub struct ChannelState {
@@ -32,6 +38,16 @@ pub struct ChannelState {
 pub struct Config {
     pub default_timeout: u64,
     pub default_gas_limit: Option<u64>,
+    /// Gas limit for forwarding incoming transfers to a contract
+    #[serde(default = "default_forward_gas_limit")]
+    pub forward_gas_limit: u64,
+}
+
+/// Used for forwards unless another limit is set at instantiation or migration
+pub const DEFAULT_FORWARD_GAS_LIMIT: u64 = 500_000;
+
+fn default_forward_gas_limit() -> u64 {
+    DEFAULT_FORWARD_GAS_LIMIT
 }
 
 #[cw_serde]
@@ -49,6 +65,30 @@ pub struct AllowInfo {
     pub gas_limit: Option<u64>,
 }
 
+#[cw_serde]
+pub struct PendingTransfer {
+    pub sender: String,
+    pub receiver: String,
+    pub denom: String,
+    pub amount: Uint128,
+    pub timeout: Timestamp,
+}
+
+/// Passed as payload from execute_transfer to the reply handler, which learns the packet sequence
+#[cw_serde]
+pub struct SendReplyArgs {
+    pub channel: String,
+    pub transfer: PendingTransfer,
+}
+
+/// Passed as payload from ibc_packet_receive to the reply handler, to fall back to a plain
+/// transfer if the forward fails
+#[cw_serde]
+pub struct ForwardReplyArgs {
+    pub receiver: String,
+    pub contract: String,
+}
+
 #[cw_serde]
 pub struct ReplyArgs {
     pub channel: String,
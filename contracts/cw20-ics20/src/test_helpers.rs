@@ -71,6 +71,7 @@ pub fn setup(
     // instantiate an empty contract
     let instantiate_msg = InitMsg {
         default_gas_limit: None,
+        forward_gas_limit: None,
         default_timeout: DEFAULT_TIMEOUT,
         gov_contract: deps.api.addr_make("gov").to_string(),
         allowlist,
@@ -1,23 +1,28 @@
 use std::cmp::Ordering;
+use std::collections::HashSet;
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
     to_json_binary, Binary, BlockInfo, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Order,
-    Response, StdResult,
+    Reply, Response, StdResult, Storage, SubMsg, SubMsgResult, WasmMsg,
 };
 
 use cw2::set_contract_version;
 use cw3::{
-    Ballot, Proposal, ProposalListResponse, ProposalResponse, Status, Vote, VoteInfo,
-    VoteListResponse, VoteResponse, VoterDetail, VoterListResponse, VoterResponse, Votes,
+    Ballot, Cw3Event, Proposal, ProposalListResponse, ProposalOpenEvent, ProposalResponse,
+    ProposalStatusChangeEvent, Status, Vote, VoteCastEvent, VoteInfo, VoteListResponse,
+    VoteResponse, VoterDetail, VoterListResponse, VoterResponse, Votes,
 };
+use cw4::Cw4Contract;
 use cw_storage_plus::Bound;
-use cw_utils::{Expiration, ThresholdResponse};
+use cw_utils::{Duration, Expiration, ThresholdResponse};
 
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::state::{next_id, Config, BALLOTS, CONFIG, PROPOSALS, VOTERS};
+use crate::state::{
+    end_dispatch, next_id, Config, BALLOTS, CONFIG, DISPATCHING, DISPATCH_ACTIVE, PROPOSALS, VOTERS,
+};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:cw3-fixed-multisig";
@@ -30,10 +35,53 @@ pub fn instantiate(
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    if msg.voters.is_empty() {
-        return Err(ContractError::NoVoters {});
+    if matches!(
+        msg.max_voting_period,
+        Duration::Height(0) | Duration::Time(0)
+    ) {
+        return Err(ContractError::ZeroVotingPeriod {});
     }
-    let total_weight = msg.voters.iter().map(|v| v.weight).sum();
+
+    let mut voters = Vec::with_capacity(msg.voters.len());
+    let (group, total_weight) = match msg.group_addr {
+        Some(group_addr) => {
+            if !msg.voters.is_empty() {
+                return Err(ContractError::VotersWithGroup {});
+            }
+            let group = Cw4Contract(deps.api.addr_validate(&group_addr).map_err(|_| {
+                ContractError::InvalidGroup {
+                    addr: group_addr.clone(),
+                }
+            })?);
+            let total_weight = group.total_weight(&deps.querier)?;
+            (Some(group), total_weight)
+        }
+        None => {
+            if msg.voters.is_empty() {
+                return Err(ContractError::NoVoters {});
+            }
+            let mut seen = HashSet::new();
+            let mut total_weight = 0u64;
+            for voter in msg.voters {
+                let addr = deps.api.addr_validate(&voter.addr)?;
+                if !seen.insert(addr.clone()) {
+                    return Err(ContractError::DuplicateVoter { addr: voter.addr });
+                }
+                if voter.weight == 0 {
+                    if !msg.allow_zero_weight_voters {
+                        return Err(ContractError::ZeroVoterWeight { addr: voter.addr });
+                    }
+                    // accepted, but they have no rights, so they are not stored at all
+                    continue;
+                }
+                total_weight = total_weight
+                    .checked_add(voter.weight)
+                    .ok_or(ContractError::TotalWeightOverflow {})?;
+                voters.push((addr, voter.weight));
+            }
+            (None, total_weight)
+        }
+    };
 
     msg.threshold.validate(total_weight)?;
 
@@ -43,13 +91,13 @@ pub fn instantiate(
         threshold: msg.threshold,
         total_weight,
         max_voting_period: msg.max_voting_period,
+        group,
     };
     CONFIG.save(deps.storage, &cfg)?;
 
     // add all voters
-    for voter in msg.voters.iter() {
-        let key = deps.api.addr_validate(&voter.addr)?;
-        VOTERS.save(deps.storage, &key, &voter.weight)?;
+    for (addr, weight) in voters {
+        VOTERS.save(deps.storage, &addr, &weight)?;
     }
     Ok(Response::default())
 }
@@ -70,6 +118,13 @@ pub fn execute(
         } => execute_propose(deps, env, info, title, description, msgs, latest),
         ExecuteMsg::Vote { proposal_id, vote } => execute_vote(deps, env, info, proposal_id, vote),
         ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
+        ExecuteMsg::ExecuteMultiple {
+            proposal_ids,
+            fail_fast,
+        } => execute_execute_multiple(deps, env, info, proposal_ids, fail_fast),
+        ExecuteMsg::DispatchProposal { proposal_id } => {
+            execute_dispatch_proposal(deps, env, info, proposal_id)
+        }
         ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
     }
 }
@@ -84,13 +139,20 @@ pub fn execute_propose(
     // we ignore earliest
     latest: Option<Expiration>,
 ) -> Result<Response<Empty>, ContractError> {
-    // only members of the multisig can create a proposal
-    let vote_power = VOTERS
-        .may_load(deps.storage, &info.sender)?
-        .ok_or(ContractError::Unauthorized {})?;
-
     let cfg = CONFIG.load(deps.storage)?;
 
+    // only members of the multisig can create a proposal
+    let vote_power = match &cfg.group {
+        Some(group) => group.is_member(&deps.querier, &info.sender, None)?,
+        None => VOTERS.may_load(deps.storage, &info.sender)?,
+    }
+    .ok_or(ContractError::Unauthorized {})?;
+    // snapshot the total weight for this proposal
+    let total_weight = match &cfg.group {
+        Some(group) => group.total_weight(&deps.querier)?,
+        None => cfg.total_weight,
+    };
+
     // max expires also used as default
     let max_expires = cfg.max_voting_period.after(&env.block);
     let mut expires = latest.unwrap_or(max_expires);
@@ -111,7 +173,7 @@ pub fn execute_propose(
         status: Status::Open,
         votes: Votes::yes(vote_power),
         threshold: cfg.threshold,
-        total_weight: cfg.total_weight,
+        total_weight,
         proposer: info.sender.clone(),
         deposit: None,
     };
@@ -126,7 +188,25 @@ pub fn execute_propose(
     };
     BALLOTS.save(deps.storage, (id, &info.sender), &ballot)?;
 
+    let open_event = ProposalOpenEvent {
+        id,
+        proposer: &info.sender,
+        expires,
+    };
+    let vote_event = VoteCastEvent {
+        id,
+        voter: &info.sender,
+        vote: Vote::Yes,
+        weight: vote_power,
+    };
     Ok(Response::new()
+        .add_event(open_event.to_event())
+        .add_event(vote_event.to_event())
+        .add_events(ProposalStatusChangeEvent::if_changed(
+            id,
+            Status::Open,
+            prop.status,
+        ))
         .add_attribute("action", "propose")
         .add_attribute("sender", info.sender)
         .add_attribute("proposal_id", id.to_string())
@@ -140,15 +220,19 @@ pub fn execute_vote(
     proposal_id: u64,
     vote: Vote,
 ) -> Result<Response<Empty>, ContractError> {
-    // only members of the multisig with weight >= 1 can vote
-    let voter_power = VOTERS.may_load(deps.storage, &info.sender)?;
-    let vote_power = match voter_power {
-        Some(power) if power >= 1 => power,
-        _ => return Err(ContractError::Unauthorized {}),
-    };
-
+    let cfg = CONFIG.load(deps.storage)?;
     // ensure proposal exists and can be voted on
     let mut prop = PROPOSALS.load(deps.storage, proposal_id)?;
+
+    // only members of the multisig with weight >= 1 can vote
+    let vote_power = match &cfg.group {
+        // use a snapshot of "start of proposal"
+        Some(group) => group.is_voting_member(&deps.querier, &info.sender, prop.start_height)?,
+        None => VOTERS
+            .may_load(deps.storage, &info.sender)?
+            .filter(|power| *power >= 1),
+    }
+    .ok_or(ContractError::Unauthorized {})?;
     // Allow voting on Passed and Rejected proposals too,
     if ![Status::Open, Status::Passed, Status::Rejected].contains(&prop.status) {
         return Err(ContractError::NotOpen {});
@@ -168,11 +252,24 @@ pub fn execute_vote(
     })?;
 
     // update vote tally
+    let old_status = prop.status;
     prop.votes.add_vote(vote, vote_power);
     prop.update_status(&env.block);
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
 
+    let vote_event = VoteCastEvent {
+        id: proposal_id,
+        voter: &info.sender,
+        vote,
+        weight: vote_power,
+    };
     Ok(Response::new()
+        .add_event(vote_event.to_event())
+        .add_events(ProposalStatusChangeEvent::if_changed(
+            proposal_id,
+            old_status,
+            prop.status,
+        ))
         .add_attribute("action", "vote")
         .add_attribute("sender", info.sender)
         .add_attribute("proposal_id", proposal_id.to_string())
@@ -186,25 +283,144 @@ pub fn execute_execute(
     proposal_id: u64,
 ) -> Result<Response, ContractError> {
     // anyone can trigger this if the vote passed
+    let res = execute_proposal(deps.storage, &env.block, proposal_id)?;
+
+    Ok(res
+        .add_attribute("action", "execute")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn execute_execute_multiple(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_ids: Vec<u64>,
+    fail_fast: bool,
+) -> Result<Response, ContractError> {
+    let mut res = Response::new()
+        .add_attribute("action", "execute_multiple")
+        .add_attribute("sender", info.sender);
+
+    for proposal_id in proposal_ids {
+        res = res.add_attribute("proposal_id", proposal_id.to_string());
+        match execute_proposal(deps.storage, &env.block, proposal_id) {
+            Ok(executed) if fail_fast => {
+                res = res
+                    .add_submessages(executed.messages)
+                    .add_events(executed.events)
+                    .add_attribute("result", "executed");
+            }
+            Ok(executed) => {
+                // dispatch via the contract itself, so a failing message only reverts
+                // this proposal and is handled in reply
+                let dispatch = WasmMsg::Execute {
+                    contract_addr: env.contract.address.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::DispatchProposal { proposal_id })?,
+                    funds: vec![],
+                };
+                DISPATCHING.save(deps.storage, proposal_id, &Empty {})?;
+                res = res
+                    .add_submessage(SubMsg::reply_always(
+                        dispatch,
+                        EXECUTE_REPLY_FLAG | proposal_id,
+                    ))
+                    .add_events(executed.events)
+                    .add_attribute("result", "executed");
+            }
+            Err(err) if !fail_fast => res = res.add_attribute("result", err.to_string()),
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(res)
+}
+
+pub fn execute_dispatch_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    // only proposals queued by the running `ExecuteMultiple` may be dispatched, one at a time,
+    // so a proposal cannot replay the messages of another one
+    if info.sender != env.contract.address
+        || !DISPATCHING.has(deps.storage, proposal_id)
+        || DISPATCH_ACTIVE.exists(deps.storage)
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+    DISPATCHING.remove(deps.storage, proposal_id);
+    DISPATCH_ACTIVE.save(deps.storage, &proposal_id)?;
+
+    let prop = PROPOSALS.load(deps.storage, proposal_id)?;
+    if prop.status != Status::Executed {
+        return Err(ContractError::WrongExecuteStatus {});
+    }
+    Ok(Response::new().add_messages(prop.msgs))
+}
+
+/// Reply ids of proposals dispatched by `ExecuteMultiple` are the proposal id with this bit set
+const EXECUTE_REPLY_FLAG: u64 = 1 << 63;
+
+/// Called once the messages of a proposal dispatched by `ExecuteMultiple` ran. If they failed
+/// they were reverted, so the proposal is set back to passed and can be executed again.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, reply: Reply) -> Result<Response, ContractError> {
+    if reply.id & EXECUTE_REPLY_FLAG == 0 {
+        return Err(ContractError::UnknownReplyId { id: reply.id });
+    }
+    let proposal_id = reply.id & !EXECUTE_REPLY_FLAG;
+    end_dispatch(deps.storage, proposal_id);
+    let SubMsgResult::Err(err) = reply.result else {
+        return Ok(Response::new());
+    };
 
     let mut prop = PROPOSALS.load(deps.storage, proposal_id)?;
+    prop.status = Status::Passed;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    Ok(Response::new()
+        .add_event(
+            ProposalStatusChangeEvent {
+                id: proposal_id,
+                old: Status::Executed,
+                new: Status::Passed,
+            }
+            .to_event(),
+        )
+        .add_attribute("action", "execute_failed")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("error", err))
+}
+
+/// Marks a passed proposal as executed. Returns a response with all the proposed messages.
+fn execute_proposal(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut prop = PROPOSALS.load(storage, proposal_id)?;
     // we allow execution even after the proposal "expiration" as long as all vote come in before
     // that point. If it was approved on time, it can be executed any time.
-    prop.update_status(&env.block);
+    let old_status = prop.status;
+    prop.update_status(block);
     if prop.status != Status::Passed {
         return Err(ContractError::WrongExecuteStatus {});
     }
 
     // set it to executed
     prop.status = Status::Executed;
-    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    PROPOSALS.save(storage, proposal_id, &prop)?;
 
     // dispatch all proposed messages
-    Ok(Response::new()
-        .add_messages(prop.msgs)
-        .add_attribute("action", "execute")
-        .add_attribute("sender", info.sender)
-        .add_attribute("proposal_id", proposal_id.to_string()))
+    Ok(Response::new().add_messages(prop.msgs).add_event(
+        ProposalStatusChangeEvent {
+            id: proposal_id,
+            old: old_status,
+            new: Status::Executed,
+        }
+        .to_event(),
+    ))
 }
 
 pub fn execute_close(
@@ -228,10 +444,19 @@ pub fn execute_close(
     }
 
     // set it to failed
+    let old_status = prop.status;
     prop.status = Status::Rejected;
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
 
     Ok(Response::new()
+        .add_event(
+            ProposalStatusChangeEvent {
+                id: proposal_id,
+                old: old_status,
+                new: Status::Rejected,
+            }
+            .to_event(),
+        )
         .add_attribute("action", "close")
         .add_attribute("sender", info.sender)
         .add_attribute("proposal_id", proposal_id.to_string()))
@@ -268,7 +493,11 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
 
 fn query_threshold(deps: Deps) -> StdResult<ThresholdResponse> {
     let cfg = CONFIG.load(deps.storage)?;
-    Ok(cfg.threshold.to_response(cfg.total_weight))
+    let total_weight = match &cfg.group {
+        Some(group) => group.total_weight(&deps.querier)?,
+        None => cfg.total_weight,
+    };
+    Ok(cfg.threshold.to_response(total_weight))
 }
 
 fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<ProposalResponse> {
@@ -387,7 +616,10 @@ fn list_votes(
 
 fn query_voter(deps: Deps, voter: String) -> StdResult<VoterResponse> {
     let voter = deps.api.addr_validate(&voter)?;
-    let weight = VOTERS.may_load(deps.storage, &voter)?;
+    let weight = match CONFIG.load(deps.storage)?.group {
+        Some(group) => group.is_member(&deps.querier, &voter, None)?,
+        None => VOTERS.may_load(deps.storage, &voter)?,
+    };
     Ok(VoterResponse { weight })
 }
 
@@ -396,6 +628,18 @@ fn list_voters(
     start_after: Option<String>,
     limit: Option<u32>,
 ) -> StdResult<VoterListResponse> {
+    if let Some(group) = CONFIG.load(deps.storage)?.group {
+        let voters = group
+            .list_members(&deps.querier, start_after, limit)?
+            .into_iter()
+            .map(|member| VoterDetail {
+                addr: member.addr,
+                weight: member.weight,
+            })
+            .collect();
+        return Ok(VoterListResponse { voters });
+    }
+
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
     let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
 
@@ -416,7 +660,7 @@ fn list_voters(
 #[cfg(test)]
 mod tests {
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{coin, from_json, BankMsg, Decimal};
+    use cosmwasm_std::{coin, from_json, Addr, BankMsg, Decimal};
 
     use cw2::{get_contract_version, ContractVersion};
     use cw_utils::{Duration, Threshold};
@@ -463,9 +707,30 @@ mod tests {
         info: MessageInfo,
         threshold: Threshold,
         max_voting_period: Duration,
+    ) -> Result<Response<Empty>, ContractError> {
+        instantiate_voters(deps, info, threshold, max_voting_period, false)
+    }
+
+    // like setup_test_case, with an additional voter of zero weight
+    #[track_caller]
+    fn setup_test_case_with_zero_weight_voter(
+        deps: DepsMut,
+        info: MessageInfo,
+        threshold: Threshold,
+        max_voting_period: Duration,
+    ) -> Result<Response<Empty>, ContractError> {
+        instantiate_voters(deps, info, threshold, max_voting_period, true)
+    }
+
+    fn instantiate_voters(
+        deps: DepsMut,
+        info: MessageInfo,
+        threshold: Threshold,
+        max_voting_period: Duration,
+        zero_weight_voter: bool,
     ) -> Result<Response<Empty>, ContractError> {
         // Instantiate a contract with voters
-        let voters = vec![
+        let mut voters = vec![
             voter(&info.sender, 1),
             voter(VOTER1, 1),
             voter(VOTER2, 2),
@@ -473,17 +738,53 @@ mod tests {
             voter(VOTER4, 4),
             voter(VOTER5, 5),
             voter(VOTER6, 1),
-            voter(NOWEIGHT_VOTER, 0),
         ];
+        if zero_weight_voter {
+            voters.push(voter(NOWEIGHT_VOTER, 0));
+        }
 
         let instantiate_msg = InstantiateMsg {
             voters,
             threshold,
             max_voting_period,
+            group_addr: None,
+            allow_zero_weight_voters: zero_weight_voter,
         };
         instantiate(deps, mock_env(), info, instantiate_msg)
     }
 
+    fn open_event(
+        proposal_id: u64,
+        proposer: &str,
+        voting_period: Duration,
+    ) -> cosmwasm_std::Event {
+        ProposalOpenEvent {
+            id: proposal_id,
+            proposer: &Addr::unchecked(proposer),
+            expires: voting_period.after(&mock_env().block),
+        }
+        .to_event()
+    }
+
+    fn vote_event(proposal_id: u64, voter: &str, vote: Vote, weight: u64) -> cosmwasm_std::Event {
+        VoteCastEvent {
+            id: proposal_id,
+            voter: &Addr::unchecked(voter),
+            vote,
+            weight,
+        }
+        .to_event()
+    }
+
+    fn status_event(proposal_id: u64, old: Status, new: Status) -> cosmwasm_std::Event {
+        ProposalStatusChangeEvent {
+            id: proposal_id,
+            old,
+            new,
+        }
+        .to_event()
+    }
+
     fn get_tally(deps: Deps, proposal_id: u64) -> u64 {
         // Get all the voters on the proposal
         let voters = QueryMsg::ListVotes {
@@ -516,6 +817,8 @@ mod tests {
                 quorum: Decimal::percent(1),
             },
             max_voting_period,
+            group_addr: None,
+            allow_zero_weight_voters: false,
         };
         let err = instantiate(
             deps.as_mut(),
@@ -526,6 +829,15 @@ mod tests {
         .unwrap_err();
         assert_eq!(err, ContractError::NoVoters {});
 
+        // Voters and group at the same time fail
+        let group_msg = InstantiateMsg {
+            voters: vec![voter(OWNER, 1)],
+            group_addr: Some(SOMEBODY.to_string()),
+            ..instantiate_msg.clone()
+        };
+        let err = instantiate(deps.as_mut(), mock_env(), info.clone(), group_msg).unwrap_err();
+        assert_eq!(err, ContractError::VotersWithGroup {});
+
         // Zero required weight fails
         let instantiate_msg = InstantiateMsg {
             voters: vec![voter(OWNER, 1)],
@@ -547,6 +859,68 @@ mod tests {
             ContractError::Threshold(cw_utils::ThresholdError::UnreachableWeight {})
         );
 
+        // Zero voting period not allowed
+        let threshold = Threshold::AbsoluteCount { weight: 1 };
+        let err = setup_test_case(deps.as_mut(), info.clone(), threshold, Duration::Height(0))
+            .unwrap_err();
+        assert_eq!(err, ContractError::ZeroVotingPeriod {});
+
+        // Duplicate voters not allowed
+        let valid_msg = InstantiateMsg {
+            voters: vec![voter(OWNER, 1), voter(VOTER1, 2)],
+            threshold: Threshold::AbsoluteCount { weight: 1 },
+            max_voting_period,
+            group_addr: None,
+            allow_zero_weight_voters: false,
+        };
+        let msg = InstantiateMsg {
+            voters: vec![voter(OWNER, 1), voter(VOTER1, 2), voter(OWNER, 3)],
+            ..valid_msg.clone()
+        };
+        let err = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::DuplicateVoter {
+                addr: OWNER.to_string()
+            }
+        );
+
+        // Zero weight voters only allowed with the flag
+        let msg = InstantiateMsg {
+            voters: vec![voter(OWNER, 1), voter(NOWEIGHT_VOTER, 0)],
+            ..valid_msg.clone()
+        };
+        let err = instantiate(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::ZeroVoterWeight {
+                addr: NOWEIGHT_VOTER.to_string()
+            }
+        );
+        let msg = InstantiateMsg {
+            allow_zero_weight_voters: true,
+            ..msg
+        };
+        instantiate(mock_dependencies().as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        // Total weight must fit in a u64
+        let msg = InstantiateMsg {
+            voters: vec![voter(OWNER, u64::MAX), voter(VOTER1, 1)],
+            ..valid_msg
+        };
+        let err = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::TotalWeightOverflow {});
+
+        // Absolute threshold equal to the total weight is reachable
+        let threshold = Threshold::AbsoluteCount { weight: 17 };
+        setup_test_case(
+            mock_dependencies().as_mut(),
+            info.clone(),
+            threshold,
+            max_voting_period,
+        )
+        .unwrap();
+
         // All valid
         let threshold = Threshold::AbsoluteCount { weight: 1 };
         setup_test_case(deps.as_mut(), info, threshold, max_voting_period).unwrap();
@@ -564,14 +938,15 @@ mod tests {
     // TODO: query() tests
 
     #[test]
-    fn zero_weight_member_cant_vote() {
+    fn zero_weight_voters_are_skipped() {
         let mut deps = mock_dependencies();
 
         let threshold = Threshold::AbsoluteCount { weight: 4 };
         let voting_period = Duration::Time(2000000);
 
         let info = mock_info(OWNER, &[]);
-        setup_test_case(deps.as_mut(), info, threshold, voting_period).unwrap();
+        setup_test_case_with_zero_weight_voter(deps.as_mut(), info, threshold, voting_period)
+            .unwrap();
 
         let bank_msg = BankMsg::Send {
             to_address: SOMEBODY.into(),
@@ -579,14 +954,24 @@ mod tests {
         };
         let msgs = vec![CosmosMsg::Bank(bank_msg)];
 
-        // Voter without voting power still can create proposal
-        let info = mock_info(NOWEIGHT_VOTER, &[]);
+        // Voter without voting power is not listed
+        let voters = list_voters(deps.as_ref(), None, Some(MAX_LIMIT)).unwrap();
+        assert_eq!(voters.voters.len(), 7);
+        assert!(!voters.voters.iter().any(|v| v.addr == NOWEIGHT_VOTER));
+        let voter = query_voter(deps.as_ref(), NOWEIGHT_VOTER.to_string()).unwrap();
+        assert_eq!(voter.weight, None);
+
+        // and cannot create proposals
         let proposal = ExecuteMsg::Propose {
             title: "Rewarding somebody".to_string(),
             description: "Do we reward her?".to_string(),
             msgs,
             latest: None,
         };
+        let info = mock_info(NOWEIGHT_VOTER, &[]);
+        let err = execute(deps.as_mut(), mock_env(), info, proposal.clone()).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let info = mock_info(OWNER, &[]);
         let res = execute(deps.as_mut(), mock_env(), info, proposal).unwrap();
 
         // Get the proposal id from the logs
@@ -649,6 +1034,8 @@ mod tests {
         assert_eq!(
             res,
             Response::new()
+                .add_event(open_event(1, VOTER3, voting_period))
+                .add_event(vote_event(1, VOTER3, Vote::Yes, 3))
                 .add_attribute("action", "propose")
                 .add_attribute("sender", VOTER3)
                 .add_attribute("proposal_id", 1.to_string())
@@ -663,6 +1050,9 @@ mod tests {
         assert_eq!(
             res,
             Response::new()
+                .add_event(open_event(2, VOTER4, voting_period))
+                .add_event(vote_event(2, VOTER4, Vote::Yes, 4))
+                .add_event(status_event(2, Status::Open, Status::Passed))
                 .add_attribute("action", "propose")
                 .add_attribute("sender", VOTER4)
                 .add_attribute("proposal_id", 2.to_string())
@@ -718,6 +1108,7 @@ mod tests {
         assert_eq!(
             res,
             Response::new()
+                .add_event(vote_event(proposal_id, VOTER1, Vote::Yes, 1))
                 .add_attribute("action", "vote")
                 .add_attribute("sender", VOTER1)
                 .add_attribute("proposal_id", proposal_id.to_string())
@@ -771,6 +1162,8 @@ mod tests {
         assert_eq!(
             res,
             Response::new()
+                .add_event(vote_event(proposal_id, VOTER4, Vote::Yes, 4))
+                .add_event(status_event(proposal_id, Status::Open, Status::Passed))
                 .add_attribute("action", "vote")
                 .add_attribute("sender", VOTER4)
                 .add_attribute("proposal_id", proposal_id.to_string())
@@ -785,6 +1178,7 @@ mod tests {
         assert_eq!(
             res,
             Response::new()
+                .add_event(vote_event(proposal_id, VOTER5, Vote::Yes, 5))
                 .add_attribute("action", "vote")
                 .add_attribute("sender", VOTER5)
                 .add_attribute("proposal_id", proposal_id.to_string())
@@ -822,6 +1216,7 @@ mod tests {
         assert_eq!(
             res,
             Response::new()
+                .add_event(vote_event(proposal_id, VOTER1, Vote::No, 1))
                 .add_attribute("action", "vote")
                 .add_attribute("sender", VOTER1)
                 .add_attribute("proposal_id", proposal_id.to_string())
@@ -836,6 +1231,7 @@ mod tests {
         assert_eq!(
             res,
             Response::new()
+                .add_event(vote_event(proposal_id, VOTER4, Vote::No, 4))
                 .add_attribute("action", "vote")
                 .add_attribute("sender", VOTER4)
                 .add_attribute("proposal_id", proposal_id.to_string())
@@ -854,6 +1250,7 @@ mod tests {
         assert_eq!(
             res,
             Response::new()
+                .add_event(vote_event(proposal_id, VOTER5, Vote::No, 5))
                 .add_attribute("action", "vote")
                 .add_attribute("sender", VOTER5)
                 .add_attribute("proposal_id", proposal_id.to_string())
@@ -869,6 +1266,8 @@ mod tests {
         assert_eq!(
             res,
             Response::new()
+                .add_event(vote_event(proposal_id, VOTER2, Vote::No, 2))
+                .add_event(status_event(proposal_id, Status::Open, Status::Rejected))
                 .add_attribute("action", "vote")
                 .add_attribute("sender", VOTER2)
                 .add_attribute("proposal_id", proposal_id.to_string())
@@ -887,6 +1286,7 @@ mod tests {
         assert_eq!(
             res,
             Response::new()
+                .add_event(vote_event(proposal_id, VOTER6, Vote::Yes, 1))
                 .add_attribute("action", "vote")
                 .add_attribute("sender", VOTER6)
                 .add_attribute("proposal_id", proposal_id.to_string())
@@ -938,6 +1338,8 @@ mod tests {
         assert_eq!(
             res,
             Response::new()
+                .add_event(vote_event(proposal_id, VOTER3, Vote::Yes, 3))
+                .add_event(status_event(proposal_id, Status::Open, Status::Passed))
                 .add_attribute("action", "vote")
                 .add_attribute("sender", VOTER3)
                 .add_attribute("proposal_id", proposal_id.to_string())
@@ -958,6 +1360,7 @@ mod tests {
             res,
             Response::new()
                 .add_messages(msgs)
+                .add_event(status_event(proposal_id, Status::Passed, Status::Executed))
                 .add_attribute("action", "execute")
                 .add_attribute("sender", SOMEBODY)
                 .add_attribute("proposal_id", proposal_id.to_string())
@@ -969,6 +1372,88 @@ mod tests {
         assert_eq!(err, ContractError::WrongCloseStatus {});
     }
 
+    #[test]
+    fn test_execute_multiple_works() {
+        let mut deps = mock_dependencies();
+
+        let threshold = Threshold::AbsoluteCount { weight: 3 };
+        let voting_period = Duration::Time(2000000);
+
+        let info = mock_info(OWNER, &[]);
+        setup_test_case(deps.as_mut(), info.clone(), threshold, voting_period).unwrap();
+
+        // Propose twice
+        let bank_msg = BankMsg::Send {
+            to_address: SOMEBODY.into(),
+            amount: vec![coin(1, "BTC")],
+        };
+        let msgs = vec![CosmosMsg::Bank(bank_msg)];
+        let proposal = ExecuteMsg::Propose {
+            title: "Pay somebody".to_string(),
+            description: "Do I pay her?".to_string(),
+            msgs: msgs.clone(),
+            latest: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), proposal.clone()).unwrap();
+        let passed_id: u64 = res.attributes[2].value.parse().unwrap();
+        let res = execute(deps.as_mut(), mock_env(), info, proposal).unwrap();
+        let open_id: u64 = res.attributes[2].value.parse().unwrap();
+
+        // Only the first one passes
+        let vote = ExecuteMsg::Vote {
+            proposal_id: passed_id,
+            vote: Vote::Yes,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(VOTER3, &[]), vote).unwrap();
+
+        // With fail_fast, the open proposal aborts the batch
+        let execution = ExecuteMsg::ExecuteMultiple {
+            proposal_ids: vec![open_id, passed_id],
+            fail_fast: true,
+        };
+        let info = mock_info(SOMEBODY, &[]);
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), execution).unwrap_err();
+        assert_eq!(err, ContractError::WrongExecuteStatus {});
+
+        // Otherwise it is reported and skipped
+        let execution = ExecuteMsg::ExecuteMultiple {
+            proposal_ids: vec![open_id, passed_id],
+            fail_fast: false,
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, execution).unwrap();
+        let dispatch = WasmMsg::Execute {
+            contract_addr: mock_env().contract.address.to_string(),
+            msg: to_json_binary(&ExecuteMsg::DispatchProposal {
+                proposal_id: passed_id,
+            })
+            .unwrap(),
+            funds: vec![],
+        };
+        assert_eq!(
+            res,
+            Response::new()
+                .add_submessage(SubMsg::reply_always(
+                    dispatch,
+                    EXECUTE_REPLY_FLAG | passed_id
+                ))
+                .add_event(status_event(passed_id, Status::Passed, Status::Executed))
+                .add_attribute("action", "execute_multiple")
+                .add_attribute("sender", SOMEBODY)
+                .add_attribute("proposal_id", open_id.to_string())
+                .add_attribute("result", ContractError::WrongExecuteStatus {}.to_string())
+                .add_attribute("proposal_id", passed_id.to_string())
+                .add_attribute("result", "executed")
+        );
+        assert_eq!(
+            PROPOSALS.load(&deps.storage, open_id).unwrap().status,
+            Status::Open
+        );
+        assert_eq!(
+            PROPOSALS.load(&deps.storage, passed_id).unwrap().status,
+            Status::Executed
+        );
+    }
+
     #[test]
     fn proposal_pass_on_expiration() {
         let mut deps = mock_dependencies();
@@ -1009,6 +1494,7 @@ mod tests {
         assert_eq!(
             res,
             Response::new()
+                .add_event(vote_event(proposal_id, VOTER3, Vote::Yes, 3))
                 .add_attribute("action", "vote")
                 .add_attribute("sender", VOTER3)
                 .add_attribute("proposal_id", proposal_id.to_string())
@@ -1059,7 +1545,12 @@ mod tests {
                 .add_attribute("sender", SOMEBODY)
                 .add_attribute("proposal_id", proposal_id.to_string())
                 .attributes
-        )
+        );
+        // the stored status was still open
+        assert_eq!(
+            res.events,
+            vec![status_event(proposal_id, Status::Open, Status::Executed)]
+        );
     }
 
     #[test]
@@ -1128,6 +1619,7 @@ mod tests {
         assert_eq!(
             res,
             Response::new()
+                .add_event(status_event(proposal_id, Status::Open, Status::Rejected))
                 .add_attribute("action", "close")
                 .add_attribute("sender", SOMEBODY)
                 .add_attribute("proposal_id", proposal_id.to_string())
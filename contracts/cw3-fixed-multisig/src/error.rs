@@ -20,6 +20,24 @@ pub enum ContractError {
     #[error("No voters")]
     NoVoters {},
 
+    #[error("Group contract invalid address '{addr}'")]
+    InvalidGroup { addr: String },
+
+    #[error("Voters must be empty when using a group")]
+    VotersWithGroup {},
+
+    #[error("Voter '{addr}' is listed more than once")]
+    DuplicateVoter { addr: String },
+
+    #[error("Voter '{addr}' has zero weight")]
+    ZeroVoterWeight { addr: String },
+
+    #[error("Total weight of the voters overflows")]
+    TotalWeightOverflow {},
+
+    #[error("Max voting period cannot be zero")]
+    ZeroVotingPeriod {},
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -43,4 +61,7 @@ pub enum ContractError {
 
     #[error("Cannot close completed or passed proposals")]
     WrongCloseStatus {},
+
+    #[error("Got a submessage reply with unknown id: {id}")]
+    UnknownReplyId { id: u64 },
 }
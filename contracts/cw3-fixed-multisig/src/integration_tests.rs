@@ -1,13 +1,15 @@
 #![cfg(test)]
 
-use cosmwasm_std::{to_json_binary, Empty, Uint128, WasmMsg};
+use cosmwasm_std::{coin, coins, to_json_binary, BankMsg, Empty, Uint128, WasmMsg};
 use cw20::{BalanceResponse, MinterResponse};
 use cw20_base::msg::QueryMsg;
-use cw3::Vote;
-use cw_multi_test::{App, Contract, ContractWrapper, Executor};
-use cw_utils::{Duration, Threshold};
+use cw3::{ProposalResponse, Status, Vote, VoterResponse};
+use cw4::Member;
+use cw_multi_test::{next_block, App, Contract, ContractWrapper, Executor};
+use cw_utils::{Duration, Threshold, ThresholdResponse};
 
-use crate::contract::{execute, instantiate, query};
+use crate::contract::{execute, instantiate, query, reply};
+use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, Voter};
 
 fn mock_app() -> App {
@@ -15,7 +17,7 @@ fn mock_app() -> App {
 }
 
 pub fn contract_cw3_fixed_multisig() -> Box<dyn Contract<Empty>> {
-    let contract = ContractWrapper::new(execute, instantiate, query);
+    let contract = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
     Box::new(contract)
 }
 
@@ -28,6 +30,15 @@ pub fn contract_cw20() -> Box<dyn Contract<Empty>> {
     Box::new(contract)
 }
 
+pub fn contract_cw4_group() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw4_group::contract::execute,
+        cw4_group::contract::instantiate,
+        cw4_group::contract::query,
+    );
+    Box::new(contract)
+}
+
 #[test]
 // cw3 multisig account can control cw20 admin actions
 fn cw3_controls_cw20() {
@@ -56,6 +67,8 @@ fn cw3_controls_cw20() {
         ],
         threshold: Threshold::AbsoluteCount { weight: 2 },
         max_voting_period: Duration::Height(3),
+        group_addr: None,
+        allow_zero_weight_voters: false,
     };
 
     let multisig_addr = router
@@ -146,3 +159,236 @@ fn cw3_controls_cw20() {
     // compare minted amount
     assert_eq!(balance.balance, mint_amount);
 }
+
+#[test]
+// voter weights come from a cw4 group, as they were when the proposal was created
+fn group_weights_snapshot_at_proposal() {
+    let mut router = mock_app();
+
+    let admin = router.api().addr_make("admin");
+    let addr1 = router.api().addr_make("addr1");
+    let addr2 = router.api().addr_make("addr2");
+    let addr3 = router.api().addr_make("addr3");
+    let newcomer = router.api().addr_make("newcomer");
+
+    let group_id = router.store_code(contract_cw4_group());
+    let group_msg = cw4_group::msg::InstantiateMsg {
+        admin: Some(admin.to_string()),
+        members: vec![
+            Member {
+                addr: addr1.to_string(),
+                weight: 1,
+            },
+            Member {
+                addr: addr2.to_string(),
+                weight: 2,
+            },
+            Member {
+                addr: addr3.to_string(),
+                weight: 3,
+            },
+        ],
+        min_weight: None,
+    };
+    let group_addr = router
+        .instantiate_contract(group_id, admin.clone(), &group_msg, &[], "Group", None)
+        .unwrap();
+
+    let cw3_id = router.store_code(contract_cw3_fixed_multisig());
+    let cw3_instantiate_msg = InstantiateMsg {
+        voters: vec![],
+        threshold: Threshold::AbsoluteCount { weight: 3 },
+        max_voting_period: Duration::Height(10),
+        group_addr: Some(group_addr.to_string()),
+        allow_zero_weight_voters: false,
+    };
+    let multisig_addr = router
+        .instantiate_contract(
+            cw3_id,
+            admin.clone(),
+            &cw3_instantiate_msg,
+            &[],
+            "Consortium",
+            None,
+        )
+        .unwrap();
+    router.update_block(next_block);
+
+    // non-members cannot propose
+    let propose_msg = ExecuteMsg::Propose {
+        title: "Do nothing".to_string(),
+        description: "Just testing".to_string(),
+        msgs: vec![],
+        latest: None,
+    };
+    let err = router
+        .execute_contract(newcomer.clone(), multisig_addr.clone(), &propose_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    router
+        .execute_contract(addr1.clone(), multisig_addr.clone(), &propose_msg, &[])
+        .unwrap();
+    router.update_block(next_block);
+
+    // membership changes after the proposal was created
+    let update_msg = cw4_group::msg::ExecuteMsg::UpdateMembers {
+        remove: vec![addr2.to_string()],
+        add: vec![Member {
+            addr: newcomer.to_string(),
+            weight: 10,
+        }],
+    };
+    router
+        .execute_contract(admin, group_addr, &update_msg, &[])
+        .unwrap();
+    router.update_block(next_block);
+
+    // current weights are reported for the contract...
+    let threshold: ThresholdResponse = router
+        .wrap()
+        .query_wasm_smart(&multisig_addr, &crate::msg::QueryMsg::Threshold {})
+        .unwrap();
+    assert_eq!(
+        threshold,
+        ThresholdResponse::AbsoluteCount {
+            weight: 3,
+            total_weight: 14
+        }
+    );
+    let voter: VoterResponse = router
+        .wrap()
+        .query_wasm_smart(
+            &multisig_addr,
+            &crate::msg::QueryMsg::Voter {
+                address: newcomer.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(voter.weight, Some(10));
+
+    // ...but the proposal keeps the total weight from its creation
+    let prop: ProposalResponse = router
+        .wrap()
+        .query_wasm_smart(
+            &multisig_addr,
+            &crate::msg::QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(
+        prop.threshold,
+        ThresholdResponse::AbsoluteCount {
+            weight: 3,
+            total_weight: 6
+        }
+    );
+
+    // the newcomer had no weight when the proposal was created
+    let vote_msg = ExecuteMsg::Vote {
+        proposal_id: 1,
+        vote: Vote::Yes,
+    };
+    let err = router
+        .execute_contract(newcomer, multisig_addr.clone(), &vote_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    // the removed member still votes with their old weight, which passes the proposal
+    router
+        .execute_contract(addr2, multisig_addr.clone(), &vote_msg, &[])
+        .unwrap();
+    let prop: ProposalResponse = router
+        .wrap()
+        .query_wasm_smart(
+            &multisig_addr,
+            &crate::msg::QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(prop.status, Status::Passed);
+}
+
+#[test]
+fn execute_multiple_isolates_failing_proposals() {
+    let mut router = mock_app();
+    let voter = router.api().addr_make("voter");
+    let recipient = router.api().addr_make("recipient");
+
+    let cw3_id = router.store_code(contract_cw3_fixed_multisig());
+    let msg = InstantiateMsg {
+        voters: vec![Voter {
+            addr: voter.to_string(),
+            weight: 1,
+        }],
+        threshold: Threshold::AbsoluteCount { weight: 1 },
+        max_voting_period: Duration::Height(3),
+        group_addr: None,
+        allow_zero_weight_voters: false,
+    };
+    let multisig_addr = router
+        .instantiate_contract(cw3_id, voter.clone(), &msg, &[], "Multisig", None)
+        .unwrap();
+    router.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &multisig_addr, coins(5, "BTC"))
+            .unwrap()
+    });
+
+    // both proposals pass right away, but only the second one can be paid
+    for amount in [10, 2] {
+        let propose = ExecuteMsg::Propose {
+            title: "Pay".to_string(),
+            description: "Pay the recipient".to_string(),
+            msgs: vec![BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: coins(amount, "BTC"),
+            }
+            .into()],
+            latest: None,
+        };
+        router
+            .execute_contract(voter.clone(), multisig_addr.clone(), &propose, &[])
+            .unwrap();
+    }
+
+    let execute_multiple = ExecuteMsg::ExecuteMultiple {
+        proposal_ids: vec![1, 2],
+        fail_fast: false,
+    };
+    let res = router
+        .execute_contract(voter.clone(), multisig_addr.clone(), &execute_multiple, &[])
+        .unwrap();
+    assert!(res.has_event(
+        &cosmwasm_std::Event::new("wasm")
+            .add_attribute("action", "execute_failed")
+            .add_attribute("proposal_id", "1")
+    ));
+
+    let balance = router.wrap().query_balance(&recipient, "BTC").unwrap();
+    assert_eq!(balance, coin(2, "BTC"));
+    let status = |router: &App, proposal_id| {
+        let prop: ProposalResponse = router
+            .wrap()
+            .query_wasm_smart(
+                &multisig_addr,
+                &crate::msg::QueryMsg::Proposal { proposal_id },
+            )
+            .unwrap();
+        prop.status
+    };
+    // the failed proposal can be executed again
+    assert_eq!(status(&router, 1), Status::Passed);
+    assert_eq!(status(&router, 2), Status::Executed);
+
+    // only the contract itself may dispatch proposals
+    let dispatch = ExecuteMsg::DispatchProposal { proposal_id: 2 };
+    let err = router
+        .execute_contract(voter, multisig_addr.clone(), &dispatch, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    // and only while `ExecuteMultiple` runs them, so an executed proposal cannot be replayed
+    let err = router
+        .execute_contract(multisig_addr.clone(), multisig_addr.clone(), &dispatch, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+}
@@ -5,9 +5,18 @@ use cw_utils::{Duration, Expiration, Threshold};
 
 #[cw_serde]
 pub struct InstantiateMsg {
+    /// Must be empty if `group_addr` is set
     pub voters: Vec<Voter>,
     pub threshold: Threshold,
     pub max_voting_period: Duration,
+    /// If set, voter weights are read from this cw4 group instead of `voters`.
+    /// Each proposal uses the weights and total weight at the time it was created.
+    #[serde(default)]
+    pub group_addr: Option<String>,
+    /// Voters with a weight of 0 are rejected unless this is set, in which case they are
+    /// skipped. They are not stored, so they can neither vote nor create proposals.
+    #[serde(default)]
+    pub allow_zero_weight_voters: bool,
 }
 
 #[cw_serde]
@@ -33,6 +42,19 @@ pub enum ExecuteMsg {
     Execute {
         proposal_id: u64,
     },
+    /// Executes the given proposals in order. Unless `fail_fast` is set, proposals that
+    /// cannot be executed are reported in the attributes and skipped, and proposals whose
+    /// messages fail are set back to passed, without reverting the other proposals.
+    ExecuteMultiple {
+        proposal_ids: Vec<u64>,
+        fail_fast: bool,
+    },
+    /// Dispatches the messages of an executed proposal. Only callable by the contract
+    /// itself, and only for the proposals queued by the running `ExecuteMultiple`, which
+    /// uses it to run each proposal on its own.
+    DispatchProposal {
+        proposal_id: u64,
+    },
     Close {
         proposal_id: u64,
     },
@@ -1,15 +1,20 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, StdResult, Storage};
+use cosmwasm_std::{Addr, Empty, StdResult, Storage};
 
 use cw3::{Ballot, Proposal};
+use cw4::Cw4Contract;
 use cw_storage_plus::{Item, Map};
 use cw_utils::{Duration, Threshold};
 
 #[cw_serde]
 pub struct Config {
     pub threshold: Threshold,
+    /// Sum of the voter weights, or the group total weight at instantiation
     pub total_weight: u64,
     pub max_voting_period: Duration,
+    /// If set, voters are the members of this group rather than VOTERS
+    #[serde(default)]
+    pub group: Option<Cw4Contract>,
 }
 
 // unique items
@@ -20,6 +25,11 @@ pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
 pub const BALLOTS: Map<(u64, &Addr), Ballot> = Map::new("votes");
 pub const PROPOSALS: Map<u64, Proposal> = Map::new("proposals");
 
+/// Proposals queued by `ExecuteMultiple` that `DispatchProposal` may still run
+pub const DISPATCHING: Map<u64, Empty> = Map::new("dispatching");
+/// Proposal whose messages are being dispatched, no other one may be dispatched meanwhile
+pub const DISPATCH_ACTIVE: Item<u64> = Item::new("dispatch_active");
+
 // multiple-item maps
 pub const VOTERS: Map<&Addr, u64> = Map::new("voters");
 
@@ -28,3 +38,9 @@ pub fn next_id(store: &mut dyn Storage) -> StdResult<u64> {
     PROPOSAL_COUNT.save(store, &id)?;
     Ok(id)
 }
+
+/// Clears the dispatch state of a proposal once the reply of its `DispatchProposal` arrives
+pub fn end_dispatch(store: &mut dyn Storage, proposal_id: u64) {
+    DISPATCHING.remove(store, proposal_id);
+    DISPATCH_ACTIVE.remove(store);
+}
@@ -4,23 +4,28 @@ use std::cmp::Ordering;
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
     to_json_binary, Binary, BlockInfo, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Order,
-    Response, StdResult,
+    Reply, Response, StdResult, Storage, SubMsg, SubMsgResult, WasmMsg,
 };
 
 use cw2::set_contract_version;
 
 use cw3::{
-    Ballot, Proposal, ProposalListResponse, ProposalResponse, Status, Vote, VoteInfo,
-    VoteListResponse, VoteResponse, VoterDetail, VoterListResponse, VoterResponse, Votes,
+    Ballot, Cw3Event, Proposal, ProposalListResponse, ProposalOpenEvent, ProposalResponse,
+    ProposalStatusChangeEvent, Status, Vote, VoteCastEvent, VoteInfo, VoteListResponse,
+    VoteResponse, VoterDetail, VoterListResponse, VoterResponse, Votes,
 };
-use cw3_fixed_multisig::state::{next_id, BALLOTS, PROPOSALS};
-use cw4::{Cw4Contract, MemberChangedHookMsg, MemberDiff};
+use cw3_fixed_multisig::state::{
+    end_dispatch, next_id, BALLOTS, DISPATCHING, DISPATCH_ACTIVE, PROPOSALS,
+};
+use cw4::Cw4Contract;
 use cw_storage_plus::Bound;
 use cw_utils::{maybe_addr, Expiration, ThresholdResponse};
 
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::state::{Config, CONFIG};
+use crate::state::{
+    Config, MemberChangePolicy, CONFIG, MEMBER_HOOK_CURSOR, MEMBER_HOOK_PAGE, OPEN_PROPOSALS,
+};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:cw3-flex-multisig";
@@ -29,7 +34,7 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
@@ -48,16 +53,50 @@ pub fn instantiate(
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    // we need to hear about group changes to apply the policy. This fails if we are not
+    // the group admin, in which case the admin can still add the hook later
+    let mut res = Response::default();
+    if msg.member_change.is_some() {
+        let add_hook = group_addr.add_hook(env.contract.address)?;
+        res = res.add_submessage(SubMsg::reply_always(add_hook, REGISTER_HOOK_ID));
+    }
+
     let cfg = Config {
         threshold: msg.threshold,
         max_voting_period: msg.max_voting_period,
         group_addr,
         executor: msg.executor,
         proposal_deposit,
+        member_change: msg.member_change,
     };
     CONFIG.save(deps.storage, &cfg)?;
 
-    Ok(Response::default())
+    Ok(res)
+}
+
+const REGISTER_HOOK_ID: u64 = 1;
+/// Reply ids of proposals dispatched by `ExecuteMultiple` are the proposal id with this bit set
+const EXECUTE_REPLY_FLAG: u64 = 1 << 63;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, reply: Reply) -> Result<Response, ContractError> {
+    match reply.id {
+        id if id & EXECUTE_REPLY_FLAG != 0 => {
+            let proposal_id = id & !EXECUTE_REPLY_FLAG;
+            end_dispatch(deps.storage, proposal_id);
+            match reply.result {
+                SubMsgResult::Ok(_) => Ok(Response::new()),
+                SubMsgResult::Err(err) => dispatch_failed(deps, proposal_id, err),
+            }
+        }
+        REGISTER_HOOK_ID => match reply.result {
+            SubMsgResult::Ok(_) => Ok(Response::new().add_attribute("hook_registered", "true")),
+            SubMsgResult::Err(err) => Ok(Response::new()
+                .add_attribute("hook_registered", "false")
+                .add_attribute("hook_error", err)),
+        },
+        _ => Err(ContractError::UnknownReplyId { id: reply.id }),
+    }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -76,10 +115,16 @@ pub fn execute(
         } => execute_propose(deps, env, info, title, description, msgs, latest),
         ExecuteMsg::Vote { proposal_id, vote } => execute_vote(deps, env, info, proposal_id, vote),
         ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
-        ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
-        ExecuteMsg::MemberChangedHook(MemberChangedHookMsg { diffs }) => {
-            execute_membership_hook(deps, env, info, diffs)
+        ExecuteMsg::ExecuteMultiple {
+            proposal_ids,
+            fail_fast,
+        } => execute_execute_multiple(deps, env, info, proposal_ids, fail_fast),
+        ExecuteMsg::DispatchProposal { proposal_id } => {
+            execute_dispatch_proposal(deps, env, info, proposal_id)
         }
+        ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
+        // the diffs are not needed, the proposals are checked against the group itself
+        ExecuteMsg::MemberChangedHook(_) => execute_membership_hook(deps, env, info),
     }
 }
 
@@ -147,6 +192,9 @@ pub fn execute_propose(
     prop.update_status(&env.block);
     let id = next_id(deps.storage)?;
     PROPOSALS.save(deps.storage, id, &prop)?;
+    if cfg.member_change.is_some() && prop.status == Status::Open {
+        OPEN_PROPOSALS.save(deps.storage, id, &expires)?;
+    }
 
     // add the first yes vote from voter
     let ballot = Ballot {
@@ -155,7 +203,25 @@ pub fn execute_propose(
     };
     BALLOTS.save(deps.storage, (id, &info.sender), &ballot)?;
 
+    let open_event = ProposalOpenEvent {
+        id,
+        proposer: &info.sender,
+        expires,
+    };
+    let vote_event = VoteCastEvent {
+        id,
+        voter: &info.sender,
+        vote: Vote::Yes,
+        weight: vote_power,
+    };
     Ok(Response::new()
+        .add_event(open_event.to_event())
+        .add_event(vote_event.to_event())
+        .add_events(ProposalStatusChangeEvent::if_changed(
+            id,
+            Status::Open,
+            prop.status,
+        ))
         .add_messages(take_deposit_msg)
         .add_attribute("action", "propose")
         .add_attribute("sender", info.sender)
@@ -202,11 +268,27 @@ pub fn execute_vote(
     })?;
 
     // update vote tally
+    let old_status = prop.status;
     prop.votes.add_vote(vote, vote_power);
     prop.update_status(&env.block);
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    if prop.status != Status::Open {
+        OPEN_PROPOSALS.remove(deps.storage, proposal_id);
+    }
 
+    let vote_event = VoteCastEvent {
+        id: proposal_id,
+        voter: &info.sender,
+        vote,
+        weight: vote_power,
+    };
     Ok(Response::new()
+        .add_event(vote_event.to_event())
+        .add_events(ProposalStatusChangeEvent::if_changed(
+            proposal_id,
+            old_status,
+            prop.status,
+        ))
         .add_attribute("action", "vote")
         .add_attribute("sender", info.sender)
         .add_attribute("proposal_id", proposal_id.to_string())
@@ -218,36 +300,158 @@ pub fn execute_execute(
     env: Env,
     info: MessageInfo,
     proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    // a proposal that did not pass is reported before checking the executor,
+    // any error reverts the status change anyway
+    let res = execute_proposal(deps.storage, &env.block, proposal_id)?;
+    cfg.authorize(&deps.querier, &info.sender)?;
+
+    Ok(res
+        .add_attribute("action", "execute")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn execute_execute_multiple(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_ids: Vec<u64>,
+    fail_fast: bool,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    cfg.authorize(&deps.querier, &info.sender)?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "execute_multiple")
+        .add_attribute("sender", info.sender);
+
+    for proposal_id in proposal_ids {
+        res = res.add_attribute("proposal_id", proposal_id.to_string());
+        match execute_proposal(deps.storage, &env.block, proposal_id) {
+            Ok(executed) if fail_fast => {
+                res = res
+                    .add_submessages(executed.messages)
+                    .add_events(executed.events)
+                    .add_attribute("result", "executed");
+            }
+            Ok(executed) => {
+                // dispatch via the contract itself, so a failing message only reverts
+                // this proposal and is handled in reply
+                let dispatch = WasmMsg::Execute {
+                    contract_addr: env.contract.address.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::DispatchProposal { proposal_id })?,
+                    funds: vec![],
+                };
+                DISPATCHING.save(deps.storage, proposal_id, &Empty {})?;
+                res = res
+                    .add_submessage(SubMsg::reply_always(
+                        dispatch,
+                        EXECUTE_REPLY_FLAG | proposal_id,
+                    ))
+                    .add_events(executed.events)
+                    .add_attribute("result", "executed");
+            }
+            Err(err) if !fail_fast => res = res.add_attribute("result", err.to_string()),
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(res)
+}
+
+pub fn execute_dispatch_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    // only proposals queued by the running `ExecuteMultiple` may be dispatched, one at a time,
+    // so a proposal cannot replay the messages of another one
+    if info.sender != env.contract.address
+        || !DISPATCHING.has(deps.storage, proposal_id)
+        || DISPATCH_ACTIVE.exists(deps.storage)
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+    DISPATCHING.remove(deps.storage, proposal_id);
+    DISPATCH_ACTIVE.save(deps.storage, &proposal_id)?;
+
+    let prop = PROPOSALS.load(deps.storage, proposal_id)?;
+    if prop.status != Status::Executed {
+        return Err(ContractError::WrongExecuteStatus {});
+    }
+    Ok(Response::new().add_messages(proposal_messages(prop)?))
+}
+
+/// The messages of a proposal dispatched by `ExecuteMultiple` failed and were reverted
+/// (deposit refund included), so the proposal is set back to passed and can be executed again.
+fn dispatch_failed(
+    deps: DepsMut,
+    proposal_id: u64,
+    err: String,
 ) -> Result<Response, ContractError> {
     let mut prop = PROPOSALS.load(deps.storage, proposal_id)?;
+    prop.status = Status::Passed;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    Ok(Response::new()
+        .add_event(
+            ProposalStatusChangeEvent {
+                id: proposal_id,
+                old: Status::Executed,
+                new: Status::Passed,
+            }
+            .to_event(),
+        )
+        .add_attribute("action", "execute_failed")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("error", err))
+}
+
+/// Marks a passed proposal as executed. Returns a response with all the proposed messages,
+/// along with the deposit refund.
+fn execute_proposal(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut prop = PROPOSALS.load(storage, proposal_id)?;
     // we allow execution even after the proposal "expiration" as long as all vote come in before
     // that point. If it was approved on time, it can be executed any time.
-    prop.update_status(&env.block);
+    let old_status = prop.status;
+    prop.update_status(block);
     if prop.status != Status::Passed {
         return Err(ContractError::WrongExecuteStatus {});
     }
 
-    let cfg = CONFIG.load(deps.storage)?;
-    cfg.authorize(&deps.querier, &info.sender)?;
-
     // set it to executed
     prop.status = Status::Executed;
-    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
-
-    // Unconditionally refund here.
-    let response = match prop.deposit {
-        Some(deposit) => {
-            Response::new().add_message(deposit.get_return_deposit_message(&prop.proposer)?)
-        }
-        None => Response::new(),
-    };
+    PROPOSALS.save(storage, proposal_id, &prop)?;
+    OPEN_PROPOSALS.remove(storage, proposal_id);
 
     // dispatch all proposed messages
-    Ok(response
-        .add_messages(prop.msgs)
-        .add_attribute("action", "execute")
-        .add_attribute("sender", info.sender)
-        .add_attribute("proposal_id", proposal_id.to_string()))
+    Ok(Response::new()
+        .add_messages(proposal_messages(prop)?)
+        .add_event(
+            ProposalStatusChangeEvent {
+                id: proposal_id,
+                old: old_status,
+                new: Status::Executed,
+            }
+            .to_event(),
+        ))
+}
+
+/// The deposit refund followed by all proposed messages
+fn proposal_messages(prop: Proposal) -> Result<Vec<CosmosMsg>, ContractError> {
+    let mut msgs = vec![];
+    // Unconditionally refund here.
+    if let Some(deposit) = prop.deposit {
+        msgs.push(deposit.get_return_deposit_message(&prop.proposer)?);
+    }
+    msgs.extend(prop.msgs);
+    Ok(msgs)
 }
 
 pub fn execute_close(
@@ -271,8 +475,10 @@ pub fn execute_close(
     }
 
     // set it to failed
+    let old_status = prop.status;
     prop.status = Status::Rejected;
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    OPEN_PROPOSALS.remove(deps.storage, proposal_id);
 
     // Refund the deposit if we have been configured to do so.
     let mut response = Response::new();
@@ -283,6 +489,14 @@ pub fn execute_close(
     }
 
     Ok(response
+        .add_event(
+            ProposalStatusChangeEvent {
+                id: proposal_id,
+                old: old_status,
+                new: Status::Rejected,
+            }
+            .to_event(),
+        )
         .add_attribute("action", "close")
         .add_attribute("sender", info.sender)
         .add_attribute("proposal_id", proposal_id.to_string()))
@@ -290,18 +504,101 @@ pub fn execute_close(
 
 pub fn execute_membership_hook(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-    _diffs: Vec<MemberDiff>,
 ) -> Result<Response<Empty>, ContractError> {
-    // This is now a no-op
-    // But we leave the authorization check as a demo
     let cfg = CONFIG.load(deps.storage)?;
     if info.sender != cfg.group_addr.0 {
         return Err(ContractError::Unauthorized {});
     }
+    let Some(policy) = cfg.member_change else {
+        return Ok(Response::default());
+    };
 
-    Ok(Response::default())
+    // the group state is already updated when the hook is called
+    let total_weight = cfg.group_addr.total_weight(&deps.querier)?;
+
+    // at most MEMBER_HOOK_PAGE open proposals per call, continuing where the last call
+    // stopped, so the work stays bounded however many proposals are open
+    let cursor = MEMBER_HOOK_CURSOR.may_load(deps.storage)?;
+    let mut open = OPEN_PROPOSALS
+        .keys(
+            deps.storage,
+            cursor.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(MEMBER_HOOK_PAGE)
+        .collect::<StdResult<Vec<_>>>()?;
+    if open.len() < MEMBER_HOOK_PAGE {
+        let wrapped = OPEN_PROPOSALS
+            .keys(
+                deps.storage,
+                None,
+                cursor.map(Bound::inclusive),
+                Order::Ascending,
+            )
+            .take(MEMBER_HOOK_PAGE - open.len())
+            .collect::<StdResult<Vec<_>>>()?;
+        open.extend(wrapped);
+    }
+    match open.last() {
+        Some(&last) if open.len() == MEMBER_HOOK_PAGE => {
+            MEMBER_HOOK_CURSOR.save(deps.storage, &last)?
+        }
+        _ => MEMBER_HOOK_CURSOR.remove(deps.storage),
+    }
+
+    let mut res = Response::new();
+    let mut updated = 0u32;
+    for id in open {
+        let mut prop = PROPOSALS.load(deps.storage, id)?;
+        if prop.status != Status::Open || prop.expires.is_expired(&env.block) {
+            OPEN_PROPOSALS.remove(deps.storage, id);
+            continue;
+        }
+        let old_status = prop.status;
+        match policy {
+            MemberChangePolicy::UpdateTotalWeight => {
+                if prop.total_weight == total_weight {
+                    continue;
+                }
+                prop.total_weight = total_weight;
+                prop.update_status(&env.block);
+            }
+            MemberChangePolicy::RejectOrphaned => {
+                // checked against the group rather than the diffs, as this proposal
+                // may not have been visited by the call that removed its proposer
+                let member = cfg
+                    .group_addr
+                    .is_member(&deps.querier, &prop.proposer, None)?;
+                if member.is_some() {
+                    continue;
+                }
+                prop.status = Status::Rejected;
+                // Refund the deposit if we have been configured to do so.
+                if let Some(deposit) = &prop.deposit {
+                    if deposit.refund_failed_proposals {
+                        res = res.add_message(deposit.get_return_deposit_message(&prop.proposer)?)
+                    }
+                }
+            }
+        }
+        PROPOSALS.save(deps.storage, id, &prop)?;
+        if prop.status != Status::Open {
+            OPEN_PROPOSALS.remove(deps.storage, id);
+        }
+        updated += 1;
+        res = res.add_events(ProposalStatusChangeEvent::if_changed(
+            id,
+            old_status,
+            prop.status,
+        ));
+    }
+
+    Ok(res
+        .add_attribute("action", "membership_hook")
+        .add_attribute("updated_proposals", updated.to_string()))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -487,15 +784,16 @@ fn list_voters(
 
 #[cfg(test)]
 mod tests {
-    use cosmwasm_std::{coin, coins, Addr, BankMsg, Coin, Decimal, Timestamp, Uint128};
+    use cosmwasm_std::{coin, coins, Addr, Api, BankMsg, Coin, Decimal, Timestamp, Uint128};
 
     use cw2::{query_contract_info, ContractVersion};
     use cw20::{Cw20Coin, UncheckedDenom};
     use cw3::{DepositError, UncheckedDepositInfo};
-    use cw4::{Cw4ExecuteMsg, Member};
+    use cw4::{Cw4ExecuteMsg, Member, MemberChangedHookMsg, MemberDiff};
     use cw4_group::helpers::Cw4GroupContract;
     use cw_multi_test::{
-        next_block, App, AppBuilder, BankSudo, Contract, ContractWrapper, Executor, SudoMsg,
+        next_block, App, AppBuilder, AppResponse, BankSudo, Contract, ContractWrapper, Executor,
+        SudoMsg,
     };
     use cw_utils::{Duration, Threshold};
 
@@ -524,7 +822,8 @@ mod tests {
             crate::contract::execute,
             crate::contract::instantiate,
             crate::contract::query,
-        );
+        )
+        .with_reply(crate::contract::reply);
         Box::new(contract)
     }
 
@@ -561,6 +860,7 @@ mod tests {
         let msg = cw4_group::msg::InstantiateMsg {
             admin: Some(OWNER.into()),
             members,
+            min_weight: None,
         };
         app.instantiate_contract(group_id, Addr::unchecked(OWNER), &msg, &[], "group", None)
             .unwrap()
@@ -582,6 +882,7 @@ mod tests {
             max_voting_period,
             executor,
             proposal_deposit,
+            member_change: None,
         };
         app.instantiate_contract(flex_id, Addr::unchecked(OWNER), &msg, &[], "flex", None)
             .unwrap()
@@ -718,6 +1019,7 @@ mod tests {
             max_voting_period,
             executor: None,
             proposal_deposit: None,
+            member_change: None,
         };
         let err = app
             .instantiate_contract(
@@ -741,6 +1043,7 @@ mod tests {
             max_voting_period,
             executor: None,
             proposal_deposit: None,
+            member_change: None,
         };
         let err = app
             .instantiate_contract(
@@ -764,6 +1067,7 @@ mod tests {
             max_voting_period,
             executor: None,
             proposal_deposit: None,
+            member_change: None,
         };
         let flex_addr = app
             .instantiate_contract(
@@ -1351,6 +1655,303 @@ mod tests {
         );
     }
 
+    #[test]
+    fn governance_events() {
+        let init_funds = coins(10, "BTC");
+        let mut app = mock_app(&init_funds);
+
+        let threshold = Threshold::ThresholdQuorum {
+            threshold: Decimal::percent(51),
+            quorum: Decimal::percent(1),
+        };
+        let voting_period = Duration::Time(2000000);
+        let (flex_addr, _) = setup_test_case(
+            &mut app,
+            threshold,
+            voting_period,
+            init_funds,
+            true,
+            None,
+            None,
+        );
+
+        // the proposal opens with the implicit yes vote of the proposer
+        let proposal = pay_somebody_proposal();
+        let res = app
+            .execute_contract(Addr::unchecked(VOTER3), flex_addr.clone(), &proposal, &[])
+            .unwrap();
+        let proposal_id: u64 = res.custom_attrs(1)[2].value.parse().unwrap();
+        let id = proposal_id.to_string();
+        let expires = voting_period.after(&app.block_info());
+        res.assert_event(
+            &cosmwasm_std::Event::new("wasm-cw3_proposal_open")
+                .add_attribute("proposal_id", &id)
+                .add_attribute("proposer", VOTER3)
+                .add_attribute("expires", expires.to_string()),
+        );
+        res.assert_event(
+            &cosmwasm_std::Event::new("wasm-cw3_vote_cast")
+                .add_attribute("proposal_id", &id)
+                .add_attribute("voter", VOTER3)
+                .add_attribute("vote", "yes")
+                .add_attribute("weight", "3"),
+        );
+        assert!(!res
+            .events
+            .iter()
+            .any(|ev| ev.ty == "wasm-cw3_proposal_status_change"));
+
+        // a passing vote also reports the status change
+        let vote = ExecuteMsg::Vote {
+            proposal_id,
+            vote: Vote::Yes,
+        };
+        let res = app
+            .execute_contract(Addr::unchecked(VOTER4), flex_addr.clone(), &vote, &[])
+            .unwrap();
+        res.assert_event(
+            &cosmwasm_std::Event::new("wasm-cw3_vote_cast")
+                .add_attribute("proposal_id", &id)
+                .add_attribute("voter", VOTER4)
+                .add_attribute("vote", "yes")
+                .add_attribute("weight", "12"),
+        );
+        res.assert_event(
+            &cosmwasm_std::Event::new("wasm-cw3_proposal_status_change")
+                .add_attribute("proposal_id", &id)
+                .add_attribute("old_status", "open")
+                .add_attribute("new_status", "passed"),
+        );
+
+        let execution = ExecuteMsg::Execute { proposal_id };
+        let res = app
+            .execute_contract(Addr::unchecked(SOMEBODY), flex_addr, &execution, &[])
+            .unwrap();
+        res.assert_event(
+            &cosmwasm_std::Event::new("wasm-cw3_proposal_status_change")
+                .add_attribute("proposal_id", &id)
+                .add_attribute("old_status", "passed")
+                .add_attribute("new_status", "executed"),
+        );
+    }
+
+    #[test]
+    fn execute_multiple_works() {
+        let init_funds = coins(10, "BTC");
+        let mut app = mock_app(&init_funds);
+
+        let voting_period = Duration::Time(2000000);
+        let (flex_addr, _) = setup_test_case(
+            &mut app,
+            Threshold::AbsoluteCount { weight: 12 },
+            voting_period,
+            init_funds,
+            true,
+            Some(crate::state::Executor::Member), // set executor as Member of voting group
+            None,
+        );
+
+        // VOTER4 alone passes a proposal, VOTER3 does not
+        let proposal = pay_somebody_proposal();
+        let res = app
+            .execute_contract(Addr::unchecked(VOTER4), flex_addr.clone(), &proposal, &[])
+            .unwrap();
+        let passed_id: u64 = res.custom_attrs(1)[2].value.parse().unwrap();
+        let res = app
+            .execute_contract(Addr::unchecked(VOTER3), flex_addr.clone(), &proposal, &[])
+            .unwrap();
+        let open_id: u64 = res.custom_attrs(1)[2].value.parse().unwrap();
+
+        // Only members can execute
+        let execution = ExecuteMsg::ExecuteMultiple {
+            proposal_ids: vec![open_id, passed_id],
+            fail_fast: false,
+        };
+        let err = app
+            .execute_contract(
+                Addr::unchecked(SOMEBODY),
+                flex_addr.clone(),
+                &execution,
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+        let res = app
+            .execute_contract(Addr::unchecked(VOTER1), flex_addr.clone(), &execution, &[])
+            .unwrap();
+        assert_eq!(
+            res.custom_attrs(1),
+            [
+                ("action", "execute_multiple"),
+                ("sender", VOTER1),
+                ("proposal_id", open_id.to_string().as_str()),
+                (
+                    "result",
+                    ContractError::WrongExecuteStatus {}.to_string().as_str()
+                ),
+                ("proposal_id", passed_id.to_string().as_str()),
+                ("result", "executed"),
+            ],
+        );
+
+        // only the passed proposal paid out
+        let some_bal = app.wrap().query_balance(SOMEBODY, "BTC").unwrap();
+        assert_eq!(some_bal, coin(1, "BTC"));
+    }
+
+    #[test]
+    fn execute_multiple_isolates_failing_proposals() {
+        let init_funds = coins(10, "BTC");
+        let mut app = mock_app(&init_funds);
+
+        let voting_period = Duration::Time(2000000);
+        let (flex_addr, _) = setup_test_case(
+            &mut app,
+            Threshold::AbsoluteCount { weight: 4 },
+            voting_period,
+            init_funds,
+            true,
+            None,
+            None,
+        );
+
+        // both pass right away, but the multisig cannot pay the first one
+        let too_much = ExecuteMsg::Propose {
+            title: "Pay a lot".to_string(),
+            description: "More than we have".to_string(),
+            msgs: vec![BankMsg::Send {
+                to_address: SOMEBODY.into(),
+                amount: coins(100, "BTC"),
+            }
+            .into()],
+            latest: None,
+        };
+        let res = app
+            .execute_contract(Addr::unchecked(VOTER4), flex_addr.clone(), &too_much, &[])
+            .unwrap();
+        let failing_id: u64 = res.custom_attrs(1)[2].value.parse().unwrap();
+        let res = app
+            .execute_contract(
+                Addr::unchecked(VOTER4),
+                flex_addr.clone(),
+                &pay_somebody_proposal(),
+                &[],
+            )
+            .unwrap();
+        let paying_id: u64 = res.custom_attrs(1)[2].value.parse().unwrap();
+
+        let execution = ExecuteMsg::ExecuteMultiple {
+            proposal_ids: vec![failing_id, paying_id],
+            fail_fast: false,
+        };
+        let res = app
+            .execute_contract(Addr::unchecked(VOTER1), flex_addr.clone(), &execution, &[])
+            .unwrap();
+        assert!(res.has_event(
+            &cosmwasm_std::Event::new("wasm")
+                .add_attribute("action", "execute_failed")
+                .add_attribute("proposal_id", failing_id.to_string())
+        ));
+
+        // the other proposal paid out, the failed one can be executed again
+        let some_bal = app.wrap().query_balance(SOMEBODY, "BTC").unwrap();
+        assert_eq!(some_bal, coin(1, "BTC"));
+        assert_eq!(
+            query_prop(&app, &flex_addr, failing_id).status,
+            Status::Passed
+        );
+        assert_eq!(
+            query_prop(&app, &flex_addr, paying_id).status,
+            Status::Executed
+        );
+    }
+
+    #[test]
+    fn dispatch_cannot_replay_proposals() {
+        let init_funds = coins(10, "BTC");
+        let mut app = mock_app(&init_funds);
+
+        let voting_period = Duration::Time(2000000);
+        let (flex_addr, _) = setup_test_case(
+            &mut app,
+            Threshold::AbsoluteCount { weight: 4 },
+            voting_period,
+            init_funds,
+            true,
+            None,
+            None,
+        );
+
+        // proposals pass right away, as VOTER4 has enough weight
+        let propose = |app: &mut App, msg: ExecuteMsg| -> u64 {
+            let res = app
+                .execute_contract(Addr::unchecked(VOTER4), flex_addr.clone(), &msg, &[])
+                .unwrap();
+            res.custom_attrs(1)[2].value.parse().unwrap()
+        };
+        let replay = |proposal_id: u64| ExecuteMsg::Propose {
+            title: "Replay".to_string(),
+            description: "Pay again".to_string(),
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: flex_addr.to_string(),
+                msg: to_json_binary(&ExecuteMsg::DispatchProposal { proposal_id }).unwrap(),
+                funds: vec![],
+            }
+            .into()],
+            latest: None,
+        };
+
+        // an executed proposal cannot be dispatched again
+        let paying_id = propose(&mut app, pay_somebody_proposal());
+        let replay_id = propose(&mut app, replay(paying_id));
+        let execution = ExecuteMsg::Execute {
+            proposal_id: paying_id,
+        };
+        app.execute_contract(Addr::unchecked(VOTER1), flex_addr.clone(), &execution, &[])
+            .unwrap();
+        let execution = ExecuteMsg::ExecuteMultiple {
+            proposal_ids: vec![replay_id],
+            fail_fast: false,
+        };
+        let res = app
+            .execute_contract(Addr::unchecked(VOTER1), flex_addr.clone(), &execution, &[])
+            .unwrap();
+        assert!(res.has_event(
+            &cosmwasm_std::Event::new("wasm")
+                .add_attribute("action", "execute_failed")
+                .add_attribute("proposal_id", replay_id.to_string())
+        ));
+        let some_bal = app.wrap().query_balance(SOMEBODY, "BTC").unwrap();
+        assert_eq!(some_bal, coin(1, "BTC"));
+
+        // nor can a proposal queued in the same batch be dispatched early by another one
+        let paying_id = propose(&mut app, pay_somebody_proposal());
+        let replay_id = propose(&mut app, replay(paying_id));
+        let execution = ExecuteMsg::ExecuteMultiple {
+            proposal_ids: vec![replay_id, paying_id],
+            fail_fast: false,
+        };
+        let res = app
+            .execute_contract(Addr::unchecked(VOTER1), flex_addr.clone(), &execution, &[])
+            .unwrap();
+        assert!(res.has_event(
+            &cosmwasm_std::Event::new("wasm")
+                .add_attribute("action", "execute_failed")
+                .add_attribute("proposal_id", replay_id.to_string())
+        ));
+        let some_bal = app.wrap().query_balance(SOMEBODY, "BTC").unwrap();
+        assert_eq!(some_bal, coin(2, "BTC"));
+        assert_eq!(
+            query_prop(&app, &flex_addr, replay_id).status,
+            Status::Passed
+        );
+        assert_eq!(
+            query_prop(&app, &flex_addr, paying_id).status,
+            Status::Executed
+        );
+    }
+
     #[test]
     fn execute_with_executor_member() {
         let init_funds = coins(10, "BTC");
@@ -1878,6 +2479,221 @@ mod tests {
         assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
     }
 
+    // group with OWNER as admin, and a multisig reacting to its changes
+    fn setup_member_change(
+        app: &mut App,
+        threshold: Threshold,
+        policy: MemberChangePolicy,
+    ) -> (Addr, Addr) {
+        let members = vec![member(VOTER1, 1), member(VOTER2, 2), member(VOTER3, 3)];
+        let group_addr = instantiate_group(app, members);
+        let flex_id = app.store_code(contract_flex());
+        let msg = crate::msg::InstantiateMsg {
+            group_addr: group_addr.to_string(),
+            threshold,
+            max_voting_period: Duration::Time(2000),
+            executor: None,
+            proposal_deposit: None,
+            member_change: Some(policy),
+        };
+        let flex_addr = app
+            .instantiate_contract(flex_id, Addr::unchecked(OWNER), &msg, &[], "flex", None)
+            .unwrap();
+
+        // the multisig is not the group admin, so OWNER has to add the hook
+        let hooks = Cw4Contract(group_addr.clone()).hooks(&app.wrap()).unwrap();
+        assert!(hooks.is_empty());
+        let add_hook = Cw4ExecuteMsg::AddHook {
+            addr: flex_addr.to_string(),
+        };
+        app.execute_contract(Addr::unchecked(OWNER), group_addr.clone(), &add_hook, &[])
+            .unwrap();
+        app.update_block(next_block);
+        (flex_addr, group_addr)
+    }
+
+    fn remove_member(app: &mut App, group_addr: &Addr, addr: &str) -> AppResponse {
+        let update = cw4_group::msg::ExecuteMsg::UpdateMembers {
+            remove: vec![addr.to_string()],
+            add: vec![],
+        };
+        let res = app
+            .execute_contract(Addr::unchecked(OWNER), group_addr.clone(), &update, &[])
+            .unwrap();
+        app.update_block(next_block);
+        res
+    }
+
+    fn query_prop(app: &App, flex_addr: &Addr, proposal_id: u64) -> ProposalResponse {
+        app.wrap()
+            .query_wasm_smart(flex_addr, &QueryMsg::Proposal { proposal_id })
+            .unwrap()
+    }
+
+    #[test]
+    fn member_change_registers_hook() {
+        let mut app = mock_app(&[]);
+        let flex_id = app.store_code(contract_flex());
+
+        // the multisig address is known in advance, so it can be made the group admin
+        let salt = b"flex";
+        let checksum = app.wrap().query_wasm_code_info(flex_id).unwrap().checksum;
+        let creator = app.api().addr_canonicalize(OWNER).unwrap();
+        let predicted = cosmwasm_std::instantiate2_address(checksum.as_slice(), &creator, salt)
+            .map(|addr| app.api().addr_humanize(&addr).unwrap())
+            .unwrap();
+
+        let group_id = app.store_code(contract_group());
+        let msg = cw4_group::msg::InstantiateMsg {
+            admin: Some(predicted.to_string()),
+            members: vec![member(VOTER1, 1)],
+            min_weight: None,
+        };
+        let group_addr = app
+            .instantiate_contract(group_id, Addr::unchecked(OWNER), &msg, &[], "group", None)
+            .unwrap();
+
+        let msg = crate::msg::InstantiateMsg {
+            group_addr: group_addr.to_string(),
+            threshold: Threshold::AbsoluteCount { weight: 1 },
+            max_voting_period: Duration::Time(2000),
+            executor: None,
+            proposal_deposit: None,
+            member_change: Some(MemberChangePolicy::UpdateTotalWeight),
+        };
+        let flex_addr = app
+            .instantiate2_contract(
+                flex_id,
+                Addr::unchecked(OWNER),
+                &msg,
+                &[],
+                "flex",
+                None,
+                salt.to_vec(),
+            )
+            .unwrap();
+        assert_eq!(flex_addr, predicted);
+
+        let hooks = Cw4Contract(group_addr).hooks(&app.wrap()).unwrap();
+        assert_eq!(hooks, vec![flex_addr.to_string()]);
+    }
+
+    #[test]
+    fn member_change_updates_total_weight() {
+        let mut app = mock_app(&[]);
+        let threshold = Threshold::AbsolutePercentage {
+            percentage: Decimal::percent(50),
+        };
+        let (flex_addr, group_addr) =
+            setup_member_change(&mut app, threshold, MemberChangePolicy::UpdateTotalWeight);
+
+        // 2 of 6, 3 needed
+        app.execute_contract(
+            Addr::unchecked(VOTER2),
+            flex_addr.clone(),
+            &text_proposal(),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(query_prop(&app, &flex_addr, 1).status, Status::Open);
+
+        // 2 of 3 now, which is enough
+        let res = remove_member(&mut app, &group_addr, VOTER3);
+        assert!(res.has_event(
+            &cosmwasm_std::Event::new("wasm-cw3_proposal_status_change")
+                .add_attribute("proposal_id", "1")
+                .add_attribute("new_status", "passed")
+        ));
+        let prop = query_prop(&app, &flex_addr, 1);
+        assert_eq!(prop.status, Status::Passed);
+        assert_eq!(
+            prop.threshold,
+            ThresholdResponse::AbsolutePercentage {
+                percentage: Decimal::percent(50),
+                total_weight: 3
+            }
+        );
+    }
+
+    #[test]
+    fn member_change_rejects_orphaned() {
+        let mut app = mock_app(&[]);
+        let threshold = Threshold::AbsoluteCount { weight: 5 };
+        let (flex_addr, group_addr) =
+            setup_member_change(&mut app, threshold, MemberChangePolicy::RejectOrphaned);
+
+        for voter in [VOTER1, VOTER2] {
+            app.execute_contract(
+                Addr::unchecked(voter),
+                flex_addr.clone(),
+                &text_proposal(),
+                &[],
+            )
+            .unwrap();
+        }
+
+        // only the proposal of the removed member is rejected
+        remove_member(&mut app, &group_addr, VOTER1);
+        assert_eq!(query_prop(&app, &flex_addr, 1).status, Status::Rejected);
+        let prop = query_prop(&app, &flex_addr, 2);
+        assert_eq!(prop.status, Status::Open);
+        // the total weight stays as it was
+        assert_eq!(
+            prop.threshold,
+            ThresholdResponse::AbsoluteCount {
+                weight: 5,
+                total_weight: 6
+            }
+        );
+    }
+
+    #[test]
+    fn member_change_pages_open_proposals() {
+        let mut app = mock_app(&[]);
+        let threshold = Threshold::AbsolutePercentage {
+            percentage: Decimal::percent(50),
+        };
+        let (flex_addr, group_addr) =
+            setup_member_change(&mut app, threshold, MemberChangePolicy::UpdateTotalWeight);
+
+        // no limit on open proposals, a single member can open as many as they like
+        let count = MEMBER_HOOK_PAGE as u64 + 5;
+        for _ in 0..count {
+            app.execute_contract(
+                Addr::unchecked(VOTER1),
+                flex_addr.clone(),
+                &text_proposal(),
+                &[],
+            )
+            .unwrap();
+        }
+        let total_weight = |app: &App, id: u64| match query_prop(app, &flex_addr, id).threshold {
+            ThresholdResponse::AbsolutePercentage { total_weight, .. } => total_weight,
+            _ => panic!("unexpected threshold"),
+        };
+
+        // the first call only visits one page
+        let res = remove_member(&mut app, &group_addr, VOTER3);
+        assert!(res
+            .has_event(&cosmwasm_std::Event::new("wasm").add_attribute("updated_proposals", "50")));
+        assert_eq!(total_weight(&app, MEMBER_HOOK_PAGE as u64), 3);
+        assert_eq!(total_weight(&app, MEMBER_HOOK_PAGE as u64 + 1), 6);
+
+        // the next one continues with the rest, then starts over
+        remove_member(&mut app, &group_addr, VOTER2);
+        assert_eq!(total_weight(&app, count), 1);
+        assert_eq!(query_prop(&app, &flex_addr, count).status, Status::Passed);
+        assert_eq!(total_weight(&app, 45), 1);
+        assert_eq!(total_weight(&app, 46), 3);
+        assert_eq!(query_prop(&app, &flex_addr, 46).status, Status::Open);
+
+        // expired proposals are dropped by the hook
+        app.update_block(|block| block.time = block.time.plus_seconds(2000));
+        let res = remove_member(&mut app, &group_addr, VOTER1);
+        assert!(res
+            .has_event(&cosmwasm_std::Event::new("wasm").add_attribute("updated_proposals", "0")));
+    }
+
     // uses the power from the beginning of the voting period
     #[test]
     fn percentage_handles_group_changes() {
@@ -2118,6 +2934,7 @@ mod tests {
                 refund_failed_proposals: true,
                 denom: UncheckedDenom::Cw20(group_addr.to_string()),
             }),
+            member_change: None,
         };
 
         let err: ContractError = app
@@ -2146,6 +2963,7 @@ mod tests {
                 refund_failed_proposals: true,
                 denom: UncheckedDenom::Native("native".to_string()),
             }),
+            member_change: None,
         };
 
         let err: ContractError = app
@@ -39,6 +39,9 @@ pub enum ContractError {
     #[error("Cannot close completed or passed proposals")]
     WrongCloseStatus {},
 
+    #[error("Got a submessage reply with unknown id: {id}")]
+    UnknownReplyId { id: u64 },
+
     #[error("{0}")]
     Payment(#[from] PaymentError),
 
@@ -4,7 +4,7 @@ use cw3::{UncheckedDepositInfo, Vote};
 use cw4::MemberChangedHookMsg;
 use cw_utils::{Duration, Expiration, Threshold};
 
-use crate::state::Executor;
+use crate::state::{Executor, MemberChangePolicy};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -17,6 +17,10 @@ pub struct InstantiateMsg {
     pub executor: Option<Executor>,
     /// The cost of creating a proposal (if any).
     pub proposal_deposit: Option<UncheckedDepositInfo>,
+    /// How open proposals react to group changes. If set, the multisig registers itself
+    /// as a hook on the group, which only works if it is the group admin.
+    #[serde(default)]
+    pub member_change: Option<MemberChangePolicy>,
 }
 
 // TODO: add some T variants? Maybe good enough as fixed Empty for now
@@ -36,6 +40,19 @@ pub enum ExecuteMsg {
     Execute {
         proposal_id: u64,
     },
+    /// Executes the given proposals in order. Unless `fail_fast` is set, proposals that
+    /// cannot be executed are reported in the attributes and skipped, and proposals whose
+    /// messages fail are set back to passed, without reverting the other proposals.
+    ExecuteMultiple {
+        proposal_ids: Vec<u64>,
+        fail_fast: bool,
+    },
+    /// Dispatches the messages of an executed proposal. Only callable by the contract
+    /// itself, and only for the proposals queued by the running `ExecuteMultiple`, which
+    /// uses it to run each proposal on its own.
+    DispatchProposal {
+        proposal_id: u64,
+    },
     Close {
         proposal_id: u64,
     },
@@ -2,8 +2,8 @@ use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, QuerierWrapper};
 use cw3::DepositInfo;
 use cw4::Cw4Contract;
-use cw_storage_plus::Item;
-use cw_utils::{Duration, Threshold};
+use cw_storage_plus::{Item, Map};
+use cw_utils::{Duration, Expiration, Threshold};
 
 use crate::error::ContractError;
 
@@ -16,6 +16,15 @@ pub enum Executor {
     Only(Addr),
 }
 
+/// Defines how open proposals react to changes in the group, reported via MemberChangedHook
+#[cw_serde]
+pub enum MemberChangePolicy {
+    /// Open proposals use the new total weight of the group, which may pass or reject them
+    UpdateTotalWeight,
+    /// Open proposals are rejected if their proposer is removed from the group
+    RejectOrphaned,
+}
+
 #[cw_serde]
 pub struct Config {
     pub threshold: Threshold,
@@ -27,6 +36,9 @@ pub struct Config {
     pub executor: Option<Executor>,
     /// The price, if any, of creating a new proposal.
     pub proposal_deposit: Option<DepositInfo>,
+    /// None means group changes don't affect open proposals
+    #[serde(default)]
+    pub member_change: Option<MemberChangePolicy>,
 }
 
 impl Config {
@@ -55,3 +67,14 @@ impl Config {
 
 // unique items
 pub const CONFIG: Item<Config> = Item::new("config");
+
+/// Open proposals with their expiration, walked by the member hook instead of all proposals.
+/// Only filled if `Config::member_change` is set.
+pub const OPEN_PROPOSALS: Map<u64, Expiration> = Map::new("open_proposals");
+
+/// Bounds the work of the member hook: each call visits at most this many open proposals.
+pub const MEMBER_HOOK_PAGE: usize = 50;
+
+/// Last proposal visited by the member hook if it stopped at `MEMBER_HOOK_PAGE`,
+/// the next call continues after it
+pub const MEMBER_HOOK_CURSOR: Item<u64> = Item::new("member_hook_cursor");
@@ -1,8 +1,8 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    attr, to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response,
-    StdResult, SubMsg, Uint64,
+    attr, to_json_binary, Addr, Attribute, Binary, Deps, DepsMut, Env, MessageInfo, Order,
+    Response, StdResult, Storage, SubMsg, Uint64,
 };
 use cw2::set_contract_version;
 use cw4::{
@@ -15,7 +15,7 @@ use cw_utils::maybe_addr;
 use crate::error::ContractError;
 use crate::helpers::validate_unique_members;
 use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::state::{ADMIN, HOOKS, MEMBERS, TOTAL};
+use crate::state::{ADMIN, HOOKS, MEMBERS, MIN_WEIGHT, PENDING_IMPORT, TOTAL};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:cw4-group";
@@ -25,13 +25,17 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 // make use of the custom errors
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    create(deps, msg.admin, msg.members, env.block.height)?;
+    // saved first, so create already checks the initial members against it
+    if let Some(min_weight) = msg.min_weight {
+        MIN_WEIGHT.save(deps.storage, &min_weight)?;
+    }
+    create(deps.branch(), msg.admin, msg.members, env.block.height)?;
     Ok(Response::default())
 }
 
@@ -53,6 +57,7 @@ pub fn create(
 
     let mut total = Uint64::zero();
     for member in members.into_iter() {
+        assert_min_weight(deps.storage, member.weight)?;
         let member_weight = Uint64::from(member.weight);
         total = total.checked_add(member_weight)?;
         let member_addr = deps.api.addr_validate(&member.addr)?;
@@ -63,6 +68,19 @@ pub fn create(
     Ok(())
 }
 
+// non-zero weights must not be below the min_weight set at instantiation
+fn assert_min_weight(storage: &dyn Storage, weight: u64) -> Result<(), ContractError> {
+    if weight == 0 {
+        return Ok(());
+    }
+    match MIN_WEIGHT.may_load(storage)? {
+        Some(min_weight) if weight < min_weight => {
+            Err(ContractError::WeightTooLow { weight, min_weight })
+        }
+        _ => Ok(()),
+    }
+}
+
 // And declare a custom Error variant for the ones where you will want to make use of it
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
@@ -81,6 +99,14 @@ pub fn execute(
         ExecuteMsg::UpdateMembers { add, remove } => {
             execute_update_members(deps, env, info, add, remove)
         }
+        ExecuteMsg::UpdateMembersMulti { updates } => {
+            execute_update_members_multi(deps, env, info, updates)
+        }
+        ExecuteMsg::SetWeight { addr, weight } => execute_set_weight(deps, env, info, addr, weight),
+        ExecuteMsg::ImportMembers {
+            members,
+            final_chunk,
+        } => execute_import_members(deps, env, info, members, final_chunk),
         ExecuteMsg::AddHook { addr } => {
             Ok(HOOKS.execute_add_hook(&ADMIN, deps, info, api.addr_validate(&addr)?)?)
         }
@@ -91,7 +117,7 @@ pub fn execute(
 }
 
 pub fn execute_update_members(
-    mut deps: DepsMut,
+    deps: DepsMut,
     env: Env,
     info: MessageInfo,
     add: Vec<Member>,
@@ -104,8 +130,75 @@ pub fn execute_update_members(
         attr("sender", &info.sender),
     ];
 
+    apply_update(deps, env, info.sender, add, remove, attributes)
+}
+
+pub fn execute_update_members_multi(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    updates: Vec<(String, Option<u64>)>,
+) -> Result<Response, ContractError> {
+    let mut addrs: Vec<&str> = updates.iter().map(|(addr, _)| addr.as_str()).collect();
+    addrs.sort_unstable();
+    if let Some(dup) = addrs.windows(2).find(|pair| pair[0] == pair[1]) {
+        return Err(ContractError::DuplicateMember {
+            member: dup[0].to_string(),
+        });
+    }
+
+    let (mut add, mut remove) = (vec![], vec![]);
+    for (addr, weight) in updates {
+        match weight {
+            Some(weight) => add.push(Member { addr, weight }),
+            None => remove.push(addr),
+        }
+    }
+
+    let attributes = vec![
+        attr("action", "update_members_multi"),
+        attr("added", add.len().to_string()),
+        attr("removed", remove.len().to_string()),
+        attr("sender", &info.sender),
+    ];
+
+    apply_update(deps, env, info.sender, add, remove, attributes)
+}
+
+pub fn execute_set_weight(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    addr: String,
+    weight: u64,
+) -> Result<Response, ContractError> {
+    let attributes = vec![
+        attr("action", "set_weight"),
+        attr("addr", &addr),
+        attr("weight", weight.to_string()),
+        attr("sender", &info.sender),
+    ];
+
+    let (add, remove) = if weight == 0 {
+        (vec![], vec![addr])
+    } else {
+        (vec![Member { addr, weight }], vec![])
+    };
+
+    apply_update(deps, env, info.sender, add, remove, attributes)
+}
+
+// updates the members and informs all hooks about the diff
+fn apply_update(
+    mut deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    add: Vec<Member>,
+    remove: Vec<String>,
+    attributes: Vec<Attribute>,
+) -> Result<Response, ContractError> {
     // make the local update
-    let diff = update_members(deps.branch(), env.block.height, info.sender, add, remove)?;
+    let diff = update_members(deps.branch(), env.block.height, sender, add, remove)?;
     // call all registered hooks
     let messages = HOOKS.prepare_hooks(deps.storage, |h| {
         diff.clone().into_cosmos_msg(h).map(SubMsg::new)
@@ -115,6 +208,64 @@ pub fn execute_update_members(
         .add_attributes(attributes))
 }
 
+pub fn execute_import_members(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    mut members: Vec<Member>,
+    final_chunk: bool,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+    validate_unique_members(&mut members)?;
+
+    for member in members {
+        assert_min_weight(deps.storage, member.weight)?;
+        let addr = deps.api.addr_validate(&member.addr)?;
+        if PENDING_IMPORT.has(deps.storage, &addr) {
+            return Err(ContractError::AlreadyImported {
+                member: member.addr,
+            });
+        }
+        PENDING_IMPORT.save(deps.storage, &addr, &member.weight)?;
+    }
+
+    let res = Response::new()
+        .add_attribute("action", "import_members")
+        .add_attribute("final_chunk", final_chunk.to_string())
+        .add_attribute("sender", &info.sender);
+    if !final_chunk {
+        return Ok(res);
+    }
+
+    // write all staged members together with the total, so they always add up.
+    // The old weights are read now, as other updates may have happened in between
+    let staged = PENDING_IMPORT
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    PENDING_IMPORT.clear(deps.storage);
+
+    let height = env.block.height;
+    let mut total = Uint64::from(TOTAL.load(deps.storage)?);
+    let mut diffs = Vec::with_capacity(staged.len());
+    for (addr, weight) in staged {
+        let old = MEMBERS.may_load(deps.storage, &addr)?;
+        total = total.checked_sub(Uint64::from(old.unwrap_or_default()))?;
+        total = total.checked_add(Uint64::from(weight))?;
+        MEMBERS.save(deps.storage, &addr, &weight, height)?;
+        diffs.push(MemberDiff::new(addr, old, Some(weight)));
+    }
+    TOTAL.save(deps.storage, &total.u64(), height)?;
+
+    let diff = MemberChangedHookMsg { diffs };
+    // call all registered hooks
+    let messages = HOOKS.prepare_hooks(deps.storage, |h| {
+        diff.clone().into_cosmos_msg(h).map(SubMsg::new)
+    })?;
+    Ok(res
+        .add_attribute("imported", diff.diffs.len().to_string())
+        .add_submessages(messages))
+}
+
 // the logic from execute_update_members extracted for easier import
 pub fn update_members(
     deps: DepsMut,
@@ -133,6 +284,7 @@ pub fn update_members(
 
     // add all new members and update total
     for add in to_add.into_iter() {
+        assert_min_weight(deps.storage, add.weight)?;
         let add_addr = deps.api.addr_validate(&add.addr)?;
         MEMBERS.update(deps.storage, &add_addr, height, |old| -> StdResult<_> {
             total = total.checked_sub(Uint64::from(old.unwrap_or_default()))?;
@@ -170,6 +322,9 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::TotalWeight { at_height: height } => {
             to_json_binary(&query_total_weight(deps, height)?)
         }
+        QueryMsg::ExportMembers { start_after, limit } => {
+            to_json_binary(&query_export_members(deps, start_after, limit)?)
+        }
         QueryMsg::Admin {} => to_json_binary(&ADMIN.query_admin(deps)?),
         QueryMsg::Hooks {} => to_json_binary(&HOOKS.query_hooks(deps)?),
     }
@@ -202,7 +357,29 @@ pub fn query_list_members(
     start_after: Option<String>,
     limit: Option<u32>,
 ) -> StdResult<MemberListResponse> {
-    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+    list_members(deps, start_after, limit)
+}
+
+// larger pages, as exports are meant to copy the whole group
+const MAX_EXPORT_LIMIT: u32 = 300;
+const DEFAULT_EXPORT_LIMIT: u32 = 100;
+
+pub fn query_export_members(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<MemberListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_EXPORT_LIMIT).min(MAX_EXPORT_LIMIT);
+    list_members(deps, start_after, limit)
+}
+
+fn list_members(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: u32,
+) -> StdResult<MemberListResponse> {
+    let limit = limit as usize;
     let addr = maybe_addr(deps.api, start_after)?;
     let start = addr.as_ref().map(Bound::exclusive);
 
@@ -22,4 +22,10 @@ pub enum ContractError {
 
     #[error("Message contained duplicate member: {member}")]
     DuplicateMember { member: String },
+
+    #[error("Weight {weight} is below the minimum of {min_weight}")]
+    WeightTooLow { weight: u64, min_weight: u64 },
+
+    #[error("Member {member} was already imported")]
+    AlreadyImported { member: String },
 }
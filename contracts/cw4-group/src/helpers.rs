@@ -39,6 +39,27 @@ impl Cw4GroupContract {
         let msg = ExecuteMsg::UpdateMembers { remove, add };
         self.encode_msg(msg)
     }
+
+    pub fn update_members_multi(
+        &self,
+        updates: Vec<(String, Option<u64>)>,
+    ) -> StdResult<CosmosMsg> {
+        let msg = ExecuteMsg::UpdateMembersMulti { updates };
+        self.encode_msg(msg)
+    }
+
+    pub fn import_members(&self, members: Vec<Member>, final_chunk: bool) -> StdResult<CosmosMsg> {
+        let msg = ExecuteMsg::ImportMembers {
+            members,
+            final_chunk,
+        };
+        self.encode_msg(msg)
+    }
+
+    pub fn set_weight(&self, addr: String, weight: u64) -> StdResult<CosmosMsg> {
+        let msg = ExecuteMsg::SetWeight { addr, weight };
+        self.encode_msg(msg)
+    }
 }
 
 /// Sorts the slice and verifies all member addresses are unique.
@@ -7,6 +7,9 @@ pub struct InstantiateMsg {
     /// Omit it to make the group immutable.
     pub admin: Option<String>,
     pub members: Vec<Member>,
+    /// Lowest non-zero weight accepted for any member, including the initial ones.
+    /// Omit it to accept any weight.
+    pub min_weight: Option<u64>,
 }
 
 #[cw_serde]
@@ -19,6 +22,21 @@ pub enum ExecuteMsg {
         remove: Vec<String>,
         add: Vec<Member>,
     },
+    /// apply a diff to the existing members, as one list of updates.
+    /// `Some(weight)` adds or updates the member, `None` removes it.
+    /// Every address may only appear once.
+    UpdateMembersMulti { updates: Vec<(String, Option<u64>)> },
+    /// Shorthand for `UpdateMembers` touching a single member.
+    /// A weight of 0 removes the member, any other weight adds or updates it
+    /// and must not be lower than the `min_weight` set at instantiation.
+    SetWeight { addr: String, weight: u64 },
+    /// Adds members in chunks, eg. when moving a large group to a new contract. Must be called
+    /// by Admin. Members are only staged until `final_chunk` is true, then all staged members
+    /// are added together with the total weight and the hooks are informed with the full diff.
+    ImportMembers {
+        members: Vec<Member>,
+        final_chunk: bool,
+    },
     /// Add a new hook to be informed of all membership changes. Must be called by Admin
     AddHook { addr: String },
     /// Remove a hook. Must be called by Admin
@@ -42,6 +60,13 @@ pub enum QueryMsg {
         addr: String,
         at_height: Option<u64>,
     },
+    /// Like `ListMembers`, but with larger pages. The members can be passed as is to
+    /// `InstantiateMsg` or `ImportMembers` of another group.
+    #[returns(cw4::MemberListResponse)]
+    ExportMembers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     /// Shows all registered hooks.
     #[returns(cw_controllers::HooksResponse)]
     Hooks {},
@@ -4,11 +4,17 @@ use cw4::{
     TOTAL_KEY_CHECKPOINTS,
 };
 use cw_controllers::{Admin, Hooks};
-use cw_storage_plus::{SnapshotItem, SnapshotMap, Strategy};
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
 
 pub const ADMIN: Admin = Admin::new("admin");
 pub const HOOKS: Hooks = Hooks::new("cw4-hooks");
 
+/// Lowest non-zero weight accepted for any member, if configured
+pub const MIN_WEIGHT: Item<u64> = Item::new("min_weight");
+
+/// Members staged by `ImportMembers` until the final chunk arrives
+pub const PENDING_IMPORT: Map<&Addr, u64> = Map::new("pending_import");
+
 pub const TOTAL: SnapshotItem<u64> = SnapshotItem::new(
     TOTAL_KEY,
     TOTAL_KEY_CHECKPOINTS,
@@ -1,10 +1,11 @@
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-use cosmwasm_std::{from_json, Addr, Api, DepsMut, OwnedDeps, Querier, Storage, SubMsg};
+use cosmwasm_std::{attr, from_json, Addr, Api, DepsMut, OwnedDeps, Querier, Storage, SubMsg};
 use cw4::{member_key, Member, MemberChangedHookMsg, MemberDiff, TOTAL_KEY};
 use cw_controllers::{AdminError, HookError};
 
 use crate::contract::{
-    execute, instantiate, query_list_members, query_member, query_total_weight, update_members,
+    execute, instantiate, query_export_members, query_list_members, query_member,
+    query_total_weight, update_members,
 };
 use crate::msg::{ExecuteMsg, InstantiateMsg};
 use crate::state::{ADMIN, HOOKS};
@@ -30,6 +31,7 @@ fn set_up(deps: DepsMut) {
                 weight: 6,
             },
         ],
+        min_weight: None,
     };
     let info = mock_info("creator", &[]);
     instantiate(deps, mock_env(), info, msg).unwrap();
@@ -87,6 +89,7 @@ fn duplicate_members_instantiation() {
                 weight: 6,
             },
         ],
+        min_weight: None,
     };
     let info = mock_info("creator", &[]);
     let err = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap_err();
@@ -271,6 +274,366 @@ fn add_and_remove_same_member() {
     assert_users(&deps, None, Some(6), Some(5), None);
 }
 
+#[test]
+fn update_members_multi() {
+    let mut deps = mock_dependencies();
+    set_up(deps.as_mut());
+
+    // non-admin cannot update
+    let msg = ExecuteMsg::UpdateMembersMulti {
+        updates: vec![
+            (USER1.into(), None),
+            (USER2.into(), Some(2)),
+            (USER3.into(), Some(7)),
+        ],
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER1, &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, AdminError::NotAdmin {}.into());
+
+    // removes, updates and adds in one go
+    let admin_info = mock_info(INIT_ADMIN, &[]);
+    let res = execute(deps.as_mut(), mock_env(), admin_info.clone(), msg).unwrap();
+    assert_eq!(res.attributes[0], attr("action", "update_members_multi"));
+    assert_eq!(res.attributes[1], attr("added", "2"));
+    assert_eq!(res.attributes[2], attr("removed", "1"));
+    assert_users(&deps, None, Some(2), Some(7), None);
+
+    // an address may only show up once
+    let msg = ExecuteMsg::UpdateMembersMulti {
+        updates: vec![(USER1.into(), Some(5)), (USER1.into(), None)],
+    };
+    let err = execute(deps.as_mut(), mock_env(), admin_info, msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DuplicateMember {
+            member: USER1.into()
+        }
+    );
+    assert_users(&deps, None, Some(2), Some(7), None);
+}
+
+#[test]
+fn set_weight() {
+    let mut deps = mock_dependencies();
+    set_up(deps.as_mut());
+
+    // non-admin cannot update
+    let msg = ExecuteMsg::SetWeight {
+        addr: USER3.into(),
+        weight: 7,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER1, &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, AdminError::NotAdmin {}.into());
+
+    // adds a new member
+    let admin_info = mock_info(INIT_ADMIN, &[]);
+    execute(deps.as_mut(), mock_env(), admin_info.clone(), msg).unwrap();
+    assert_users(&deps, Some(11), Some(6), Some(7), None);
+
+    // updates an existing one
+    let msg = ExecuteMsg::SetWeight {
+        addr: USER1.into(),
+        weight: 3,
+    };
+    execute(deps.as_mut(), mock_env(), admin_info.clone(), msg).unwrap();
+    assert_users(&deps, Some(3), Some(6), Some(7), None);
+
+    // weight 0 removes the member
+    let msg = ExecuteMsg::SetWeight {
+        addr: USER2.into(),
+        weight: 0,
+    };
+    execute(deps.as_mut(), mock_env(), admin_info, msg).unwrap();
+    assert_users(&deps, Some(3), None, Some(7), None);
+}
+
+#[test]
+fn set_weight_respects_min_weight() {
+    let mut deps = mock_dependencies();
+    let msg = InstantiateMsg {
+        admin: Some(INIT_ADMIN.into()),
+        members: vec![Member {
+            addr: USER1.into(),
+            weight: 11,
+        }],
+        min_weight: Some(5),
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+    let admin_info = mock_info(INIT_ADMIN, &[]);
+    let msg = ExecuteMsg::SetWeight {
+        addr: USER2.into(),
+        weight: 4,
+    };
+    let err = execute(deps.as_mut(), mock_env(), admin_info.clone(), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::WeightTooLow {
+            weight: 4,
+            min_weight: 5
+        }
+    );
+
+    // the minimum itself is fine
+    let msg = ExecuteMsg::SetWeight {
+        addr: USER2.into(),
+        weight: 5,
+    };
+    execute(deps.as_mut(), mock_env(), admin_info.clone(), msg).unwrap();
+
+    // removal is not affected by the minimum
+    let msg = ExecuteMsg::SetWeight {
+        addr: USER1.into(),
+        weight: 0,
+    };
+    execute(deps.as_mut(), mock_env(), admin_info, msg).unwrap();
+    assert_users(&deps, None, Some(5), None, None);
+}
+
+#[test]
+fn min_weight_applies_to_all_updates() {
+    let too_low = ContractError::WeightTooLow {
+        weight: 4,
+        min_weight: 5,
+    };
+
+    // initial members are checked
+    let mut deps = mock_dependencies();
+    let msg = InstantiateMsg {
+        admin: Some(INIT_ADMIN.into()),
+        members: vec![Member {
+            addr: USER1.into(),
+            weight: 4,
+        }],
+        min_weight: Some(5),
+    };
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap_err();
+    assert_eq!(err, too_low);
+
+    let msg = InstantiateMsg {
+        admin: Some(INIT_ADMIN.into()),
+        members: vec![Member {
+            addr: USER1.into(),
+            weight: 11,
+        }],
+        min_weight: Some(5),
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+    let admin_info = mock_info(INIT_ADMIN, &[]);
+
+    let msg = ExecuteMsg::UpdateMembers {
+        add: vec![Member {
+            addr: USER2.into(),
+            weight: 4,
+        }],
+        remove: vec![],
+    };
+    let err = execute(deps.as_mut(), mock_env(), admin_info.clone(), msg).unwrap_err();
+    assert_eq!(err, too_low);
+
+    let msg = ExecuteMsg::UpdateMembersMulti {
+        updates: vec![(USER2.into(), Some(4))],
+    };
+    let err = execute(deps.as_mut(), mock_env(), admin_info.clone(), msg).unwrap_err();
+    assert_eq!(err, too_low);
+
+    let msg = ExecuteMsg::ImportMembers {
+        members: vec![Member {
+            addr: USER2.into(),
+            weight: 4,
+        }],
+        final_chunk: true,
+    };
+    let err = execute(deps.as_mut(), mock_env(), admin_info.clone(), msg).unwrap_err();
+    assert_eq!(err, too_low);
+
+    // zero weight members are still allowed
+    let msg = ExecuteMsg::UpdateMembers {
+        add: vec![Member {
+            addr: USER2.into(),
+            weight: 0,
+        }],
+        remove: vec![],
+    };
+    execute(deps.as_mut(), mock_env(), admin_info, msg).unwrap();
+    assert_users(&deps, Some(11), Some(0), None, None);
+}
+
+#[test]
+fn set_weight_fires_hooks() {
+    let mut deps = mock_dependencies();
+    set_up(deps.as_mut());
+
+    let contract1 = deps.api.addr_make("hook1").to_string();
+    let admin_info = mock_info(INIT_ADMIN, &[]);
+    let add_msg = ExecuteMsg::AddHook {
+        addr: contract1.clone(),
+    };
+    execute(deps.as_mut(), mock_env(), admin_info.clone(), add_msg).unwrap();
+
+    let msg = ExecuteMsg::SetWeight {
+        addr: USER1.into(),
+        weight: 0,
+    };
+    let res = execute(deps.as_mut(), mock_env(), admin_info, msg).unwrap();
+
+    let hook_msg = MemberChangedHookMsg {
+        diffs: vec![MemberDiff::new(USER1, Some(11), None)],
+    };
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(hook_msg.into_cosmos_msg(contract1).unwrap())]
+    );
+}
+
+#[test]
+fn import_members_in_chunks() {
+    let mut deps = mock_dependencies();
+    set_up(deps.as_mut());
+
+    let contract1 = deps.api.addr_make("hook1").to_string();
+    let admin_info = mock_info(INIT_ADMIN, &[]);
+    let add_msg = ExecuteMsg::AddHook {
+        addr: contract1.clone(),
+    };
+    execute(deps.as_mut(), mock_env(), admin_info.clone(), add_msg).unwrap();
+
+    let chunk1 = ExecuteMsg::ImportMembers {
+        members: vec![Member {
+            addr: USER3.into(),
+            weight: 4,
+        }],
+        final_chunk: false,
+    };
+
+    // only the admin can import
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(USER1, &[]),
+        chunk1.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, AdminError::NotAdmin {}.into());
+
+    // first chunk is only staged
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        admin_info.clone(),
+        chunk1.clone(),
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+    assert_users(&deps, Some(11), Some(6), None, None);
+
+    // cannot stage the same member twice
+    let err = execute(deps.as_mut(), mock_env(), admin_info.clone(), chunk1).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::AlreadyImported {
+            member: USER3.into()
+        }
+    );
+
+    // other updates keep working while the import is pending
+    let msg = ExecuteMsg::UpdateMembers {
+        add: vec![Member {
+            addr: USER3.into(),
+            weight: 2,
+        }],
+        remove: vec![USER2.into()],
+    };
+    execute(deps.as_mut(), mock_env(), admin_info.clone(), msg).unwrap();
+    assert_users(&deps, Some(11), None, Some(2), None);
+
+    // final chunk applies everything on top of them and informs the hooks once
+    let chunk2 = ExecuteMsg::ImportMembers {
+        members: vec![Member {
+            addr: USER1.into(),
+            weight: 20,
+        }],
+        final_chunk: true,
+    };
+    let res = execute(deps.as_mut(), mock_env(), admin_info, chunk2).unwrap();
+    assert_users(&deps, Some(20), None, Some(4), None);
+
+    let mut diffs = vec![
+        MemberDiff::new(USER1, Some(11), Some(20)),
+        MemberDiff::new(USER3, Some(2), Some(4)),
+    ];
+    diffs.sort_by(|a, b| a.key.cmp(&b.key));
+    let hook_msg = MemberChangedHookMsg { diffs };
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(hook_msg.into_cosmos_msg(contract1).unwrap())]
+    );
+}
+
+#[test]
+fn export_members_feeds_import() {
+    let mut deps = mock_dependencies();
+    set_up(deps.as_mut());
+
+    let page = query_export_members(deps.as_ref(), None, Some(1)).unwrap();
+    assert_eq!(page.members.len(), 1);
+    let rest =
+        query_export_members(deps.as_ref(), Some(page.members[0].addr.clone()), None).unwrap();
+    assert_eq!(rest.members.len(), 1);
+
+    // export pages are larger than list pages
+    let members = (0..50)
+        .map(|i| Member {
+            addr: deps.api.addr_make(&format!("member{i}")).to_string(),
+            weight: 1,
+        })
+        .collect();
+    let msg = ExecuteMsg::ImportMembers {
+        members,
+        final_chunk: true,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(INIT_ADMIN, &[]), msg).unwrap();
+    let listed = query_list_members(deps.as_ref(), None, Some(100)).unwrap();
+    assert_eq!(listed.members.len(), 30);
+    let exported = query_export_members(deps.as_ref(), None, None).unwrap();
+    assert_eq!(exported.members.len(), 52);
+
+    // the export instantiates an identical group
+    let mut new_deps = mock_dependencies();
+    let msg = InstantiateMsg {
+        admin: None,
+        members: exported.members.clone(),
+        min_weight: None,
+    };
+    instantiate(
+        new_deps.as_mut(),
+        mock_env(),
+        mock_info("creator", &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        query_export_members(new_deps.as_ref(), None, None).unwrap(),
+        exported
+    );
+    assert_eq!(
+        query_total_weight(new_deps.as_ref(), None).unwrap().weight,
+        67
+    );
+}
+
 #[test]
 fn add_remove_hooks() {
     // add will over-write and remove have no effect
@@ -1,22 +1,29 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    coins, from_json, to_json_binary, Addr, BankMsg, Binary, Deps, DepsMut, Env, MessageInfo,
-    Order, Response, StdResult, Storage, SubMsg, Uint128, WasmMsg,
+    coins, from_json, to_json_binary, Addr, BankMsg, Binary, BlockInfo, Decimal, Deps, DepsMut,
+    Env, MessageInfo, Order, Response, StdResult, Storage, SubMsg, Timestamp, Uint128, Uint64,
+    WasmMsg,
 };
 
-use cw2::set_contract_version;
+use cw2::{ensure_from_older_version, set_contract_version};
 use cw20::{Balance, Cw20CoinVerified, Cw20ExecuteMsg, Cw20ReceiveMsg, Denom};
 use cw4::{
     Member, MemberChangedHookMsg, MemberDiff, MemberListResponse, MemberResponse,
     TotalWeightResponse,
 };
 use cw_storage_plus::Bound;
-use cw_utils::{maybe_addr, NativeBalance};
+use cw_utils::{maybe_addr, Duration, Expiration, NativeBalance};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, ReceiveMsg, StakedResponse};
-use crate::state::{Config, ADMIN, CLAIMS, CONFIG, HOOKS, MEMBERS, STAKE, TOTAL};
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, PendingReward, PendingRewardsResponse, QueryMsg,
+    ReceiveMsg, RewardTokensResponse, StakedResponse,
+};
+use crate::state::{
+    reward_key, Config, ADMIN, CLAIMS, CONFIG, HOOKS, MEMBERS, PENDING_REWARDS, REWARD_PER_TOKEN,
+    REWARD_TOKENS, STAKE, TOTAL, USER_REWARD_PER_TOKEN,
+};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:cw4-stake";
@@ -27,7 +34,7 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     mut deps: DepsMut,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
@@ -38,11 +45,15 @@ pub fn instantiate(
     // min_bond is at least 1, so 0 stake -> non-membership
     let min_bond = std::cmp::max(msg.min_bond, Uint128::new(1));
 
+    // an unbonding period that cannot be added to the block would lock all bonds forever
+    unbonding_expiration(msg.unbonding_period, &env.block)?;
+
     let config = Config {
         denom: msg.denom,
         tokens_per_weight: msg.tokens_per_weight,
         min_bond,
         unbonding_period: msg.unbonding_period,
+        allow_small_bonds: msg.allow_small_bonds.unwrap_or_default(),
     };
     CONFIG.save(deps.storage, &config)?;
     TOTAL.save(deps.storage, &0)?;
@@ -72,6 +83,10 @@ pub fn execute(
         ExecuteMsg::Bond {} => execute_bond(deps, env, Balance::from(info.funds), info.sender),
         ExecuteMsg::Unbond { tokens: amount } => execute_unbond(deps, env, info, amount),
         ExecuteMsg::Claim {} => execute_claim(deps, env, info),
+        ExecuteMsg::AddRewardToken { token } => execute_add_reward_token(deps, info, token),
+        ExecuteMsg::DistributeRewards {} => {
+            execute_distribute_rewards(deps, Balance::from(info.funds))
+        }
         ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
     }
 }
@@ -102,9 +117,16 @@ pub fn execute_bond(
     }?;
 
     // update the sender's stake
-    let new_stake = STAKE.update(deps.storage, &sender, |stake| -> StdResult<_> {
-        Ok(stake.unwrap_or_default() + amount)
-    })?;
+    let new_stake = STAKE.may_load(deps.storage, &sender)?.unwrap_or_default() + amount;
+    // a stake below min_bond gives no weight, reject it rather than silently
+    // locking the funds
+    if new_stake < cfg.min_bond && !cfg.allow_small_bonds {
+        return Err(ContractError::BondTooSmall {
+            min_bond: cfg.min_bond,
+            received: amount,
+        });
+    }
+    STAKE.save(deps.storage, &sender, &new_stake)?;
 
     let messages = update_membership(
         deps.storage,
@@ -141,7 +163,72 @@ pub fn execute_receive(
         ReceiveMsg::Bond {} => {
             execute_bond(deps, env, balance, api.addr_validate(&wrapper.sender)?)
         }
+        ReceiveMsg::DistributeRewards {} => execute_distribute_rewards(deps, balance),
+    }
+}
+
+pub fn execute_add_reward_token(
+    deps: DepsMut,
+    info: MessageInfo,
+    token: Denom,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+    if let Denom::Cw20(addr) = &token {
+        deps.api.addr_validate(addr.as_str())?;
+    }
+
+    let mut tokens = REWARD_TOKENS.may_load(deps.storage)?.unwrap_or_default();
+    if tokens.contains(&token) {
+        return Err(ContractError::RewardTokenExists {});
+    }
+    let key = reward_key(&token);
+    tokens.push(token);
+    REWARD_TOKENS.save(deps.storage, &tokens)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_reward_token")
+        .add_attribute("token", key)
+        .add_attribute("sender", info.sender))
+}
+
+pub fn execute_distribute_rewards(
+    deps: DepsMut,
+    amount: Balance,
+) -> Result<Response, ContractError> {
+    if amount.is_empty() {
+        return Err(ContractError::NoFunds {});
     }
+    let total = TOTAL.load(deps.storage)?;
+    if total == 0 {
+        return Err(ContractError::NoWeight {});
+    }
+
+    let rewards: Vec<_> = match amount {
+        Balance::Native(have) => have
+            .into_vec()
+            .into_iter()
+            .map(|coin| (Denom::Native(coin.denom), coin.amount))
+            .collect(),
+        Balance::Cw20(have) => vec![(Denom::Cw20(have.address), have.amount)],
+    };
+
+    let tokens = REWARD_TOKENS.may_load(deps.storage)?.unwrap_or_default();
+    if let Some((denom, _)) = rewards.iter().find(|(denom, _)| !tokens.contains(denom)) {
+        return Err(ContractError::UnknownRewardToken(reward_key(denom)));
+    }
+
+    let mut res = Response::new().add_attribute("action", "distribute_rewards");
+    for (denom, amount) in rewards {
+        let key = reward_key(&denom);
+        REWARD_PER_TOKEN.update(deps.storage, &key, |index| {
+            Decimal::checked_from_ratio(amount, total)
+                .ok()
+                .and_then(|share| index.unwrap_or_default().checked_add(share).ok())
+                .ok_or_else(|| ContractError::RewardOverflow(coin_to_string(amount, &key)))
+        })?;
+        res = res.add_attribute("rewards", coin_to_string(amount, &key));
+    }
+    Ok(res)
 }
 
 pub fn execute_unbond(
@@ -150,19 +237,16 @@ pub fn execute_unbond(
     info: MessageInfo,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let expires = unbonding_expiration(cfg.unbonding_period, &env.block)?;
+
     // reduce the sender's stake - aborting if insufficient
     let new_stake = STAKE.update(deps.storage, &info.sender, |stake| -> StdResult<_> {
         Ok(stake.unwrap_or_default().checked_sub(amount)?)
     })?;
 
     // provide them a claim
-    let cfg = CONFIG.load(deps.storage)?;
-    CLAIMS.create_claim(
-        deps.storage,
-        &info.sender,
-        amount,
-        cfg.unbonding_period.after(&env.block),
-    )?;
+    CLAIMS.create_claim(deps.storage, &info.sender, amount, expires)?;
 
     let messages = update_membership(
         deps.storage,
@@ -210,6 +294,8 @@ fn update_membership(
     if new == old {
         return Ok(vec![]);
     }
+    // rewards accrued so far were earned with the old weight
+    settle_rewards(storage, &sender, old.unwrap_or_default())?;
     // otherwise, record change of weight
     match new.as_ref() {
         Some(w) => MEMBERS.save(storage, &sender, w, height),
@@ -230,6 +316,62 @@ fn update_membership(
     })
 }
 
+/// Rewards of every reward token accrued by `addr` since the last settlement,
+/// given the weight it held during that time.
+fn unsettled_rewards(
+    storage: &dyn Storage,
+    addr: &Addr,
+    weight: u64,
+) -> StdResult<Vec<(Denom, Decimal, Uint128)>> {
+    REWARD_TOKENS
+        .may_load(storage)?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|token| {
+            let key = reward_key(&token);
+            let index = REWARD_PER_TOKEN
+                .may_load(storage, &key)?
+                .unwrap_or_default();
+            let paid = USER_REWARD_PER_TOKEN
+                .may_load(storage, (addr, &key))?
+                .unwrap_or_default();
+            let earned = Uint128::from(weight).mul_floor(index - paid);
+            Ok((token, index, earned))
+        })
+        .collect()
+}
+
+/// Moves all rewards accrued by `addr` to `PENDING_REWARDS`. Must be called
+/// before any change of the member's weight.
+fn settle_rewards(storage: &mut dyn Storage, addr: &Addr, weight: u64) -> StdResult<()> {
+    for (token, index, earned) in unsettled_rewards(storage, addr, weight)? {
+        let key = reward_key(&token);
+        if !earned.is_zero() {
+            PENDING_REWARDS.update(storage, (addr, &key), |pending| -> StdResult<_> {
+                Ok(pending.unwrap_or_default() + earned)
+            })?;
+        }
+        USER_REWARD_PER_TOKEN.save(storage, (addr, &key), &index)?;
+    }
+    Ok(())
+}
+
+// like Duration::after, but fails instead of panicking if the period is absurdly long
+fn unbonding_expiration(period: Duration, block: &BlockInfo) -> StdResult<Expiration> {
+    let expires = match period {
+        Duration::Height(height) => {
+            let height = Uint64::new(block.height).checked_add(Uint64::new(height))?;
+            Expiration::AtHeight(height.u64())
+        }
+        Duration::Time(seconds) => {
+            let nanos = Uint64::new(seconds).checked_mul(Uint64::new(1_000_000_000))?;
+            let nanos = Uint64::new(block.time.nanos()).checked_add(nanos)?;
+            Expiration::AtTime(Timestamp::from_nanos(nanos.u64()))
+        }
+    };
+    Ok(expires)
+}
+
 fn calc_weight(stake: Uint128, cfg: &Config) -> Option<u64> {
     if stake < cfg.min_bond {
         None
@@ -245,41 +387,72 @@ pub fn execute_claim(
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
     let release = CLAIMS.claim_tokens(deps.storage, &info.sender, &env.block, None)?;
-    if release.is_zero() {
+
+    // collect all rewards, including the ones accrued since the last weight change
+    let weight = MEMBERS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    settle_rewards(deps.storage, &info.sender, weight)?;
+    let mut rewards = vec![];
+    for token in REWARD_TOKENS.may_load(deps.storage)?.unwrap_or_default() {
+        let key = reward_key(&token);
+        let pending = PENDING_REWARDS
+            .may_load(deps.storage, (&info.sender, &key))?
+            .unwrap_or_default();
+        if !pending.is_zero() {
+            PENDING_REWARDS.remove(deps.storage, (&info.sender, &key));
+            rewards.push((token, pending));
+        }
+    }
+
+    if release.is_zero() && rewards.is_empty() {
         return Err(ContractError::NothingToClaim {});
     }
 
     let config = CONFIG.load(deps.storage)?;
-    let (amount_str, message) = match &config.denom {
-        Denom::Native(denom) => {
-            let amount_str = coin_to_string(release, denom.as_str());
-            let amount = coins(release.u128(), denom);
-            let message = SubMsg::new(BankMsg::Send {
-                to_address: info.sender.to_string(),
-                amount,
-            });
-            (amount_str, message)
-        }
+    let mut res = Response::new()
+        .add_attribute("action", "claim")
+        .add_attribute("sender", &info.sender);
+    if !release.is_zero() {
+        res = res
+            .add_submessage(send_tokens(&info.sender, release, &config.denom)?)
+            .add_attribute("tokens", coin_to_string(release, denom_str(&config.denom)));
+    }
+    for (token, amount) in rewards {
+        res = res
+            .add_submessage(send_tokens(&info.sender, amount, &token)?)
+            .add_attribute("rewards", coin_to_string(amount, denom_str(&token)));
+    }
+    Ok(res)
+}
+
+fn send_tokens(to: &Addr, amount: Uint128, denom: &Denom) -> StdResult<SubMsg> {
+    let msg = match denom {
+        Denom::Native(denom) => SubMsg::new(BankMsg::Send {
+            to_address: to.to_string(),
+            amount: coins(amount.u128(), denom),
+        }),
         Denom::Cw20(addr) => {
-            let amount_str = coin_to_string(release, addr.as_str());
             let transfer = Cw20ExecuteMsg::Transfer {
-                recipient: info.sender.clone().into(),
-                amount: release,
+                recipient: to.into(),
+                amount,
             };
-            let message = SubMsg::new(WasmMsg::Execute {
+            SubMsg::new(WasmMsg::Execute {
                 contract_addr: addr.into(),
                 msg: to_json_binary(&transfer)?,
                 funds: vec![],
-            });
-            (amount_str, message)
+            })
         }
     };
+    Ok(msg)
+}
 
-    Ok(Response::new()
-        .add_submessage(message)
-        .add_attribute("action", "claim")
-        .add_attribute("tokens", amount_str)
-        .add_attribute("sender", info.sender))
+#[inline]
+fn denom_str(denom: &Denom) -> &str {
+    match denom {
+        Denom::Native(denom) => denom.as_str(),
+        Denom::Cw20(addr) => addr.as_str(),
+    }
 }
 
 #[inline]
@@ -304,9 +477,36 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Staked { address } => to_json_binary(&query_staked(deps, address)?),
         QueryMsg::Admin {} => to_json_binary(&ADMIN.query_admin(deps)?),
         QueryMsg::Hooks {} => to_json_binary(&HOOKS.query_hooks(deps)?),
+        QueryMsg::RewardTokens {} => to_json_binary(&query_reward_tokens(deps)?),
+        QueryMsg::PendingRewards { address } => {
+            to_json_binary(&query_pending_rewards(deps, address)?)
+        }
     }
 }
 
+pub fn query_reward_tokens(deps: Deps) -> StdResult<RewardTokensResponse> {
+    let tokens = REWARD_TOKENS.may_load(deps.storage)?.unwrap_or_default();
+    Ok(RewardTokensResponse { tokens })
+}
+
+pub fn query_pending_rewards(deps: Deps, addr: String) -> StdResult<PendingRewardsResponse> {
+    let addr = deps.api.addr_validate(&addr)?;
+    let weight = MEMBERS.may_load(deps.storage, &addr)?.unwrap_or_default();
+    let rewards = unsettled_rewards(deps.storage, &addr, weight)?
+        .into_iter()
+        .map(|(denom, _, earned)| {
+            let pending = PENDING_REWARDS
+                .may_load(deps.storage, (&addr, &reward_key(&denom)))?
+                .unwrap_or_default();
+            Ok(PendingReward {
+                denom,
+                amount: pending + earned,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+    Ok(PendingRewardsResponse { rewards })
+}
+
 fn query_total_weight(deps: Deps) -> StdResult<TotalWeightResponse> {
     let weight = TOTAL.load(deps.storage)?;
     Ok(TotalWeightResponse { weight })
@@ -355,6 +555,19 @@ fn list_members(
     Ok(MemberListResponse { members })
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if let Some(allow_small_bonds) = msg.allow_small_bonds {
+        CONFIG.update(deps.storage, |mut cfg| -> StdResult<_> {
+            cfg.allow_small_bonds = allow_small_bonds;
+            Ok(cfg)
+        })?;
+    }
+    Ok(Response::default())
+}
+
 #[cfg(test)]
 mod tests {
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
@@ -388,6 +601,18 @@ mod tests {
             TOKENS_PER_WEIGHT,
             MIN_BOND,
             Duration::Height(UNBONDING_BLOCKS),
+            false,
+        )
+    }
+
+    // accepts bonds below MIN_BOND, giving them no weight
+    fn small_bonds_instantiate(deps: DepsMut) {
+        do_instantiate(
+            deps,
+            TOKENS_PER_WEIGHT,
+            MIN_BOND,
+            Duration::Height(UNBONDING_BLOCKS),
+            true,
         )
     }
 
@@ -396,12 +621,14 @@ mod tests {
         tokens_per_weight: Uint128,
         min_bond: Uint128,
         unbonding_period: Duration,
+        allow_small_bonds: bool,
     ) {
         let msg = InstantiateMsg {
             denom: Denom::Native("stake".to_string()),
             tokens_per_weight,
             min_bond,
             unbonding_period,
+            allow_small_bonds: Some(allow_small_bonds),
             admin: Some(INIT_ADMIN.into()),
         };
         let info = mock_info("creator", &[]);
@@ -414,6 +641,8 @@ mod tests {
             tokens_per_weight: TOKENS_PER_WEIGHT,
             min_bond: MIN_BOND,
             unbonding_period,
+            // the cw20 tests bond below MIN_BOND
+            allow_small_bonds: Some(true),
             admin: Some(INIT_ADMIN.into()),
         };
         let info = mock_info("creator", &[]);
@@ -538,7 +767,7 @@ mod tests {
     #[test]
     fn bond_stake_adds_membership() {
         let mut deps = mock_dependencies();
-        default_instantiate(deps.as_mut());
+        small_bonds_instantiate(deps.as_mut());
         let height = mock_env().block.height;
 
         // Assert original weights
@@ -568,7 +797,7 @@ mod tests {
     #[test]
     fn unbond_stake_update_membership() {
         let mut deps = mock_dependencies();
-        default_instantiate(deps.as_mut());
+        small_bonds_instantiate(deps.as_mut());
         let height = mock_env().block.height;
 
         // ensure it rounds down, and respects cut-off
@@ -623,6 +852,54 @@ mod tests {
         assert_users(deps.as_ref(), Some(12), Some(7), None, None);
     }
 
+    #[test]
+    fn unbonding_period_overflow() {
+        for period in [Duration::Height(u64::MAX), Duration::Time(u64::MAX)] {
+            let msg = InstantiateMsg {
+                denom: Denom::Native("stake".to_string()),
+                tokens_per_weight: TOKENS_PER_WEIGHT,
+                min_bond: MIN_BOND,
+                unbonding_period: period,
+                allow_small_bonds: None,
+                admin: Some(INIT_ADMIN.into()),
+            };
+            let err = instantiate(
+                mock_dependencies().as_mut(),
+                mock_env(),
+                mock_info("creator", &[]),
+                msg,
+            )
+            .unwrap_err();
+            assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+        }
+
+        // a period that only overflows later still fails unbond without panicking
+        let mut deps = mock_dependencies();
+        let period = Duration::Height(u64::MAX - mock_env().block.height);
+        do_instantiate(deps.as_mut(), TOKENS_PER_WEIGHT, MIN_BOND, period, false);
+        bond(deps.as_mut(), 12_000, 0, 0, 1);
+        let msg = ExecuteMsg::Unbond {
+            tokens: Uint128::new(1_000),
+        };
+        let mut env = mock_env();
+        env.block.height += 1;
+        let err = execute(deps.as_mut(), env, mock_info(USER1, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+        assert_stake(deps.as_ref(), 12_000, 0, 0);
+
+        // the largest period that still fits is fine
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let period = Duration::Height(u64::MAX - env.block.height);
+        do_instantiate(deps.as_mut(), TOKENS_PER_WEIGHT, MIN_BOND, period, false);
+        bond(deps.as_mut(), 12_000, 0, 0, 0);
+        unbond(deps.as_mut(), 1_000, 0, 0, 0);
+        assert_eq!(
+            get_claims(deps.as_ref(), &Addr::unchecked(USER1)),
+            vec![Claim::new(1_000, Expiration::AtHeight(u64::MAX))]
+        );
+    }
+
     #[test]
     fn cw20_token_claim() {
         let unbonding_period: u64 = 50;
@@ -712,7 +989,7 @@ mod tests {
     #[test]
     fn unbond_claim_workflow() {
         let mut deps = mock_dependencies();
-        default_instantiate(deps.as_mut());
+        small_bonds_instantiate(deps.as_mut());
 
         // create some data
         bond(deps.as_mut(), 12_000, 7_500, 4_000, 1);
@@ -843,6 +1120,226 @@ mod tests {
         assert_eq!(get_claims(deps.as_ref(), &Addr::unchecked(USER2)), vec![]);
     }
 
+    const REWARD_DENOM: &str = "reward";
+    const REWARD_CW20: &str = addr!("reward-token");
+
+    fn add_reward_token(deps: DepsMut, token: Denom) {
+        let msg = ExecuteMsg::AddRewardToken { token };
+        execute(deps, mock_env(), mock_info(INIT_ADMIN, &[]), msg).unwrap();
+    }
+
+    fn distribute_cw20(deps: DepsMut, amount: u128) -> Result<Response, ContractError> {
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: INIT_ADMIN.to_string(),
+            amount: Uint128::new(amount),
+            msg: to_json_binary(&ReceiveMsg::DistributeRewards {}).unwrap(),
+        });
+        execute(deps, mock_env(), mock_info(REWARD_CW20, &[]), msg)
+    }
+
+    fn get_pending_rewards(deps: Deps, addr: &str) -> Vec<u128> {
+        query_pending_rewards(deps, addr.into())
+            .unwrap()
+            .rewards
+            .into_iter()
+            .map(|r| r.amount.u128())
+            .collect()
+    }
+
+    #[test]
+    fn add_reward_tokens() {
+        let mut deps = mock_dependencies();
+        default_instantiate(deps.as_mut());
+
+        // non-admin cannot add
+        let msg = ExecuteMsg::AddRewardToken {
+            token: Denom::Native(REWARD_DENOM.into()),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(USER1, &[]), msg).unwrap_err();
+        assert_eq!(err, AdminError::NotAdmin {}.into());
+
+        // admin can add native and cw20 tokens
+        add_reward_token(deps.as_mut(), Denom::Native(REWARD_DENOM.into()));
+        add_reward_token(deps.as_mut(), Denom::Cw20(Addr::unchecked(REWARD_CW20)));
+        assert_eq!(
+            query_reward_tokens(deps.as_ref()).unwrap().tokens,
+            vec![
+                Denom::Native(REWARD_DENOM.into()),
+                Denom::Cw20(Addr::unchecked(REWARD_CW20))
+            ]
+        );
+
+        // but not twice
+        let msg = ExecuteMsg::AddRewardToken {
+            token: Denom::Native(REWARD_DENOM.into()),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(INIT_ADMIN, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::RewardTokenExists {});
+    }
+
+    #[test]
+    fn distribute_rewards_by_weight() {
+        let mut deps = mock_dependencies();
+        default_instantiate(deps.as_mut());
+        add_reward_token(deps.as_mut(), Denom::Native(REWARD_DENOM.into()));
+        add_reward_token(deps.as_mut(), Denom::Cw20(Addr::unchecked(REWARD_CW20)));
+
+        // nobody to distribute to yet
+        let info = mock_info(INIT_ADMIN, &coins(1_800, REWARD_DENOM));
+        let msg = ExecuteMsg::DistributeRewards {};
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(err, ContractError::NoWeight {});
+
+        bond(deps.as_mut(), 12_000, 6_000, 0, 1);
+
+        // unknown tokens are rejected
+        let info = mock_info(INIT_ADMIN, &[coin(1_800, REWARD_DENOM), coin(10, "FOO")]);
+        let msg = ExecuteMsg::DistributeRewards {};
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::UnknownRewardToken("native:FOO".to_string())
+        );
+
+        // split 12:6
+        let info = mock_info(INIT_ADMIN, &coins(1_800, REWARD_DENOM));
+        let msg = ExecuteMsg::DistributeRewards {};
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        distribute_cw20(deps.as_mut(), 900).unwrap();
+        assert_eq!(get_pending_rewards(deps.as_ref(), USER1), vec![1_200, 600]);
+        assert_eq!(get_pending_rewards(deps.as_ref(), USER2), vec![600, 300]);
+        assert_eq!(get_pending_rewards(deps.as_ref(), USER3), vec![0, 0]);
+
+        // late joiner does not get earlier rewards, weights are now 12:6:6
+        bond(deps.as_mut(), 0, 0, 6_000, 2);
+        let info = mock_info(INIT_ADMIN, &coins(2_400, REWARD_DENOM));
+        let msg = ExecuteMsg::DistributeRewards {};
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(get_pending_rewards(deps.as_ref(), USER1), vec![2_400, 600]);
+        assert_eq!(get_pending_rewards(deps.as_ref(), USER2), vec![1_200, 300]);
+        assert_eq!(get_pending_rewards(deps.as_ref(), USER3), vec![600, 0]);
+
+        // unbonding keeps what was already earned, but stops further accrual
+        unbond(deps.as_mut(), 0, 6_000, 0, 3);
+        distribute_cw20(deps.as_mut(), 1_800).unwrap();
+        assert_eq!(
+            get_pending_rewards(deps.as_ref(), USER1),
+            vec![2_400, 1_800]
+        );
+        assert_eq!(get_pending_rewards(deps.as_ref(), USER2), vec![1_200, 300]);
+        assert_eq!(get_pending_rewards(deps.as_ref(), USER3), vec![600, 600]);
+    }
+
+    #[test]
+    fn distribute_rewards_overflow() {
+        let mut deps = mock_dependencies();
+        default_instantiate(deps.as_mut());
+        add_reward_token(deps.as_mut(), Denom::Cw20(Addr::unchecked(REWARD_CW20)));
+        bond(deps.as_mut(), 5_000, 0, 0, 1);
+
+        // the per-token share does not fit in a Decimal
+        let err = distribute_cw20(deps.as_mut(), u128::MAX).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::RewardOverflow(format!("{} cw20:{}", u128::MAX, REWARD_CW20))
+        );
+
+        // the share fits, but adding it to the index a second time does not
+        let big = u128::MAX / 1_000_000_000_000_000_000 * 3;
+        distribute_cw20(deps.as_mut(), big).unwrap();
+        let err = distribute_cw20(deps.as_mut(), big).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::RewardOverflow(format!("{} cw20:{}", big, REWARD_CW20))
+        );
+    }
+
+    #[test]
+    fn claim_rewards() {
+        let mut deps = mock_dependencies();
+        default_instantiate(deps.as_mut());
+        add_reward_token(deps.as_mut(), Denom::Native(REWARD_DENOM.into()));
+        add_reward_token(deps.as_mut(), Denom::Cw20(Addr::unchecked(REWARD_CW20)));
+
+        bond(deps.as_mut(), 12_000, 6_000, 0, 1);
+        let info = mock_info(INIT_ADMIN, &coins(1_800, REWARD_DENOM));
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::DistributeRewards {},
+        )
+        .unwrap();
+        distribute_cw20(deps.as_mut(), 900).unwrap();
+
+        // rewards can be claimed without any unbonding
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER1, &[]),
+            ExecuteMsg::Claim {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(BankMsg::Send {
+                    to_address: USER1.into(),
+                    amount: coins(1_200, REWARD_DENOM),
+                }),
+                SubMsg::new(WasmMsg::Execute {
+                    contract_addr: REWARD_CW20.into(),
+                    msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: USER1.into(),
+                        amount: Uint128::new(600),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }),
+            ]
+        );
+        assert_eq!(get_pending_rewards(deps.as_ref(), USER1), vec![0, 0]);
+        // no stake was released, so there is no tokens attribute
+        assert!(!res.attributes.iter().any(|attr| attr.key == "tokens"));
+
+        // nothing left to claim
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER1, &[]),
+            ExecuteMsg::Claim {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NothingToClaim {});
+
+        // released stake and rewards are paid out together
+        unbond(deps.as_mut(), 0, 6_000, 0, 2);
+        let mut env = mock_env();
+        env.block.height += 2 + UNBONDING_BLOCKS;
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_info(USER2, &[]),
+            ExecuteMsg::Claim {},
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 3);
+        assert_eq!(
+            res.messages[0],
+            SubMsg::new(BankMsg::Send {
+                to_address: USER2.into(),
+                amount: coins(6_000, DENOM),
+            })
+        );
+        assert_eq!(
+            res.messages[1],
+            SubMsg::new(BankMsg::Send {
+                to_address: USER2.into(),
+                amount: coins(600, REWARD_DENOM),
+            })
+        );
+    }
+
     #[test]
     fn add_remove_hooks() {
         // add will over-write and remove have no effect
@@ -987,11 +1484,102 @@ mod tests {
         assert_eq!(err, ContractError::ExtraDenoms(DENOM.to_string()));
 
         // can bond with just the proper denom
-        // cannot bond with incorrect denom
-        let info = mock_info(USER1, &[coin(500, DENOM)]);
+        let info = mock_info(USER1, &[coin(MIN_BOND.u128(), DENOM)]);
         execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Bond {}).unwrap();
     }
 
+    #[test]
+    fn reject_bonds_below_min_bond() {
+        let mut deps = mock_dependencies();
+        default_instantiate(deps.as_mut());
+
+        // a first bond below min_bond is rejected and nothing is staked
+        let info = mock_info(USER1, &[coin(4_000, DENOM)]);
+        let err = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Bond {}).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BondTooSmall {
+                min_bond: MIN_BOND,
+                received: Uint128::new(4_000),
+            }
+        );
+        assert_stake(deps.as_ref(), 0, 0, 0);
+        assert_users(deps.as_ref(), None, None, None, None);
+
+        // bonding enough in one go is fine
+        bond(deps.as_mut(), 6_000, 0, 0, 1);
+        assert_users(deps.as_ref(), Some(6), None, None, None);
+
+        // a top-up is judged on the resulting stake, not the amount sent
+        bond(deps.as_mut(), 1_000, 0, 0, 2);
+        assert_stake(deps.as_ref(), 7_000, 0, 0);
+
+        // unbonding below min_bond is still allowed, but a top-up that does not
+        // reach the minimum again is rejected
+        let env = mock_env();
+        let info = mock_info(USER1, &[]);
+        let msg = ExecuteMsg::Unbond {
+            tokens: Uint128::new(4_000),
+        };
+        execute(deps.as_mut(), env, info, msg).unwrap();
+        assert_stake(deps.as_ref(), 3_000, 0, 0);
+        let info = mock_info(USER1, &[coin(1_000, DENOM)]);
+        let err = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Bond {}).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BondTooSmall {
+                min_bond: MIN_BOND,
+                received: Uint128::new(1_000),
+            }
+        );
+        assert_stake(deps.as_ref(), 3_000, 0, 0);
+
+        // topping up past the minimum restores the weight
+        bond(deps.as_mut(), 2_000, 0, 0, 3);
+        assert_stake(deps.as_ref(), 5_000, 0, 0);
+        assert_users(deps.as_ref(), Some(5), None, None, None);
+    }
+
+    #[test]
+    fn small_bonds_allowed_when_configured() {
+        let mut deps = mock_dependencies();
+        small_bonds_instantiate(deps.as_mut());
+
+        // the stake is kept but gives no weight
+        bond(deps.as_mut(), 4_000, 0, 0, 1);
+        assert_stake(deps.as_ref(), 4_000, 0, 0);
+        assert_users(deps.as_ref(), None, None, None, None);
+    }
+
+    #[test]
+    fn migrate_toggles_small_bonds() {
+        let mut deps = mock_dependencies();
+        default_instantiate(deps.as_mut());
+
+        // leaving the flag out keeps the current config
+        migrate(
+            deps.as_mut(),
+            mock_env(),
+            MigrateMsg {
+                allow_small_bonds: None,
+            },
+        )
+        .unwrap();
+        assert!(!CONFIG.load(&deps.storage).unwrap().allow_small_bonds);
+
+        migrate(
+            deps.as_mut(),
+            mock_env(),
+            MigrateMsg {
+                allow_small_bonds: Some(true),
+            },
+        )
+        .unwrap();
+        assert!(CONFIG.load(&deps.storage).unwrap().allow_small_bonds);
+        bond(deps.as_mut(), 4_000, 0, 0, 1);
+        assert_stake(deps.as_ref(), 4_000, 0, 0);
+    }
+
     #[test]
     fn ensure_bonding_edge_cases() {
         // use min_bond 0, tokens_per_weight 500
@@ -1001,6 +1589,7 @@ mod tests {
             Uint128::new(100),
             Uint128::zero(),
             Duration::Height(5),
+            false,
         );
 
         // setting 50 tokens, gives us Some(0) weight
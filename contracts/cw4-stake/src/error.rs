@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 use thiserror::Error;
 
 use cw_controllers::{AdminError, HookError};
@@ -37,4 +37,22 @@ pub enum ContractError {
 
     #[error("No data in ReceiveMsg")]
     NoData {},
+
+    #[error("Bonding {received} leaves the stake below the minimum bond of {min_bond}")]
+    BondTooSmall {
+        min_bond: Uint128,
+        received: Uint128,
+    },
+
+    #[error("Reward token already added")]
+    RewardTokenExists {},
+
+    #[error("Not a reward token: {0}")]
+    UnknownRewardToken(String),
+
+    #[error("No member weight to distribute rewards to")]
+    NoWeight {},
+
+    #[error("Distributing {0} overflows the reward index")]
+    RewardOverflow(String),
 }
@@ -12,6 +12,9 @@ pub struct InstantiateMsg {
     pub tokens_per_weight: Uint128,
     pub min_bond: Uint128,
     pub unbonding_period: Duration,
+    /// If set, bonds leaving the stake below `min_bond` are accepted and simply
+    /// give no weight. By default they are rejected.
+    pub allow_small_bonds: Option<bool>,
 
     // admin can only add/remove hooks, not change other parameters
     pub admin: Option<String>,
@@ -19,15 +22,23 @@ pub struct InstantiateMsg {
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    /// Bond will bond all staking tokens sent with the message and update membership weight
+    /// Bond will bond all staking tokens sent with the message and update membership weight.
+    /// Fails if the resulting stake is below `min_bond`, unless `allow_small_bonds` is set
     Bond {},
     /// Unbond will start the unbonding process for the given number of tokens.
     /// The sender immediately loses weight from these tokens, and can claim them
     /// back to his wallet after `unbonding_period`
     Unbond { tokens: Uint128 },
     /// Claim is used to claim your native tokens that you previously "unbonded"
-    /// after the contract-defined waiting period (eg. 1 week)
+    /// after the contract-defined waiting period (eg. 1 week), along with all
+    /// rewards accrued so far in every reward token
     Claim {},
+    /// Registers an additional token that can be distributed as staking reward.
+    /// Must be called by Admin
+    AddRewardToken { token: Denom },
+    /// Distributes all native reward tokens sent with the message to the current
+    /// members, proportionally to their weight
+    DistributeRewards {},
 
     /// Change the admin
     UpdateAdmin { admin: Option<String> },
@@ -40,10 +51,20 @@ pub enum ExecuteMsg {
     Receive(Cw20ReceiveMsg),
 }
 
+#[cw_serde]
+pub struct MigrateMsg {
+    /// Overrides the `allow_small_bonds` config. Contracts migrated from versions
+    /// without this option are strict unless it is set here.
+    pub allow_small_bonds: Option<bool>,
+}
+
 #[cw_serde]
 pub enum ReceiveMsg {
-    /// Only valid cw20 message is to bond the tokens
+    /// Bond the tokens
     Bond {},
+    /// Distribute the tokens as reward to the current members, proportionally
+    /// to their weight. The cw20 must have been registered with `AddRewardToken`
+    DistributeRewards {},
 }
 
 #[cw_serde]
@@ -73,6 +94,12 @@ pub enum QueryMsg {
     /// Shows all registered hooks.
     #[returns(cw_controllers::HooksResponse)]
     Hooks {},
+    /// Shows all tokens distributed as rewards
+    #[returns(RewardTokensResponse)]
+    RewardTokens {},
+    /// Shows the rewards this address can currently claim, per reward token
+    #[returns(PendingRewardsResponse)]
+    PendingRewards { address: String },
 }
 
 #[cw_serde]
@@ -80,3 +107,19 @@ pub struct StakedResponse {
     pub stake: Uint128,
     pub denom: Denom,
 }
+
+#[cw_serde]
+pub struct RewardTokensResponse {
+    pub tokens: Vec<Denom>,
+}
+
+#[cw_serde]
+pub struct PendingReward {
+    pub denom: Denom,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct PendingRewardsResponse {
+    pub rewards: Vec<PendingReward>,
+}
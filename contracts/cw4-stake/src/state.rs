@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Decimal, Uint128};
 use cw20::Denom;
 use cw4::TOTAL_KEY;
 use cw_controllers::{Admin, Claims, Hooks};
@@ -15,6 +15,9 @@ pub struct Config {
     pub tokens_per_weight: Uint128,
     pub min_bond: Uint128,
     pub unbonding_period: Duration,
+    /// accept bonds leaving the stake below `min_bond` (without weight)
+    #[serde(default)]
+    pub allow_small_bonds: bool,
 }
 
 pub const ADMIN: Admin = Admin::new("admin");
@@ -30,3 +33,21 @@ pub const MEMBERS: SnapshotMap<&Addr, u64> = SnapshotMap::new(
 );
 
 pub const STAKE: Map<&Addr, Uint128> = Map::new("stake");
+
+/// Tokens distributed as staking rewards, in the order they were added
+pub const REWARD_TOKENS: Item<Vec<Denom>> = Item::new("reward_tokens");
+/// Accumulated reward per unit of weight, keyed by `reward_key` of the reward token
+pub const REWARD_PER_TOKEN: Map<&str, Decimal> = Map::new("reward_per_token");
+/// Value of `REWARD_PER_TOKEN` the last time the member's rewards were settled
+pub const USER_REWARD_PER_TOKEN: Map<(&Addr, &str), Decimal> = Map::new("user_reward_per_token");
+/// Rewards settled for a member, but not yet claimed
+pub const PENDING_REWARDS: Map<(&Addr, &str), Uint128> = Map::new("pending_rewards");
+
+/// Storage key of a reward token. Prefixed, as a native denom may look just like
+/// a cw20 address.
+pub fn reward_key(denom: &Denom) -> String {
+    match denom {
+        Denom::Native(denom) => format!("native:{denom}"),
+        Denom::Cw20(addr) => format!("cw20:{addr}"),
+    }
+}
@@ -0,0 +1,39 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, Binary, CosmosMsg, StdResult, Uint128, WasmMsg};
+
+/// Cw20BurnHookMsg should be de/serialized under `BeforeBurn()` variant in a ExecuteMsg.
+/// It is sent to the registered burn hook as part of every burn, after the balance and
+/// supply are updated. If the hook returns an error, the whole burn is reverted.
+#[cw_serde]
+pub struct Cw20BurnHookMsg {
+    /// The account whose tokens are burned
+    pub burner: String,
+    pub amount: Uint128,
+    /// Total supply after this burn
+    pub remaining_supply: Uint128,
+}
+
+impl Cw20BurnHookMsg {
+    /// serializes the message
+    pub fn into_json_binary(self) -> StdResult<Binary> {
+        let msg = BurnHookExecuteMsg::BeforeBurn(self);
+        to_json_binary(&msg)
+    }
+
+    /// creates a cosmos_msg sending this struct to the named contract
+    pub fn into_cosmos_msg<T: Into<String>>(self, contract_addr: T) -> StdResult<CosmosMsg> {
+        let msg = self.into_json_binary()?;
+        let execute = WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg,
+            funds: vec![],
+        };
+        Ok(execute.into())
+    }
+}
+
+// This is just a helper to properly serialize the above message
+#[cw_serde]
+enum BurnHookExecuteMsg {
+    BeforeBurn(Cw20BurnHookMsg),
+}
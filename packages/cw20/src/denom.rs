@@ -16,6 +16,7 @@ pub enum UncheckedDenom {
 }
 
 #[cw_serde]
+#[allow(dead_code)]
 pub struct DepositInfo {
     amount: Uint128,
     denom: UncheckedDenom,
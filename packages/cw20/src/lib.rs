@@ -12,19 +12,21 @@ For more information on this specification, please check out the
 pub use cw_utils::Expiration;
 
 pub use crate::balance::Balance;
+pub use crate::burn_hook::Cw20BurnHookMsg;
 pub use crate::coin::{Cw20Coin, Cw20CoinVerified};
 pub use crate::denom::{Denom, UncheckedDenom};
 pub use crate::helpers::Cw20Contract;
 pub use crate::logo::{EmbeddedLogo, Logo, LogoInfo};
-pub use crate::msg::Cw20ExecuteMsg;
+pub use crate::msg::{BatchSendItem, Cw20ExecuteMsg};
 pub use crate::query::{
     AllAccountsResponse, AllAllowancesResponse, AllSpenderAllowancesResponse, AllowanceInfo,
-    AllowanceResponse, BalanceResponse, Cw20QueryMsg, DownloadLogoResponse, MarketingInfoResponse,
-    MinterResponse, SpenderAllowanceInfo, TokenInfoResponse,
+    AllowanceResponse, BalanceResponse, Cw20QueryMsg, DownloadLogoResponse, FullTokenInfoResponse,
+    MarketingInfoResponse, MinterResponse, SpenderAllowanceInfo, TokenInfoResponse,
 };
 pub use crate::receiver::Cw20ReceiveMsg;
 
 mod balance;
+mod burn_hook;
 mod coin;
 mod denom;
 mod helpers;
@@ -1,3 +1,4 @@
+use crate::coin::Cw20Coin;
 use crate::logo::Logo;
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Binary, Uint128};
@@ -17,6 +18,24 @@ pub enum Cw20ExecuteMsg {
         amount: Uint128,
         msg: Binary,
     },
+    /// Moves tokens from the `env.sender` account to every listed recipient, in order.
+    /// Fails as a whole if the sender cannot cover the sum of all amounts.
+    BatchTransfer { transfers: Vec<Cw20Coin> },
+    /// Like `Send`, but for multiple recipient contracts. Every contract is notified
+    /// with its own `Cw20ReceiveMsg`, in order. Fails as a whole if the sender cannot
+    /// cover the sum of all amounts.
+    BatchSend { sends: Vec<BatchSendItem> },
+    /// Like `Send`, but the receiving contract is notified with "reply on error". If its
+    /// `Receive` handler fails, the transfer is NOT reverted: the tokens stay with `contract`
+    /// and the failure is recorded in a `cw20_send_loose_failed` event.
+    ///
+    /// Only use this for contracts that can deal with tokens they were never notified about
+    /// (eg. routers with a sweep function). Otherwise the tokens are stranded there.
+    SendLoose {
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
     /// Only with "approval" extension. Allows spender to access an additional amount tokens
     /// from the owner's (env.sender) account. If expires is Some(), overwrites current allowance
     /// expiration with this one.
@@ -33,6 +52,17 @@ pub enum Cw20ExecuteMsg {
         amount: Uint128,
         expires: Option<Expiration>,
     },
+    /// Only with "approval" extension. Sets the spender's access of tokens from the owner's
+    /// (env.sender) account to exactly amount, but only if the current allowance equals
+    /// `expected_current`. This prevents the spender from front-running the update and using
+    /// both the old and the new allowance. If expires is Some(), overwrites current allowance
+    /// expiration with this one.
+    SetAllowanceConditional {
+        spender: String,
+        amount: Uint128,
+        expected_current: Uint128,
+        expires: Option<Expiration>,
+    },
     /// Only with "approval" extension. Transfers amount tokens from owner -> recipient
     /// if `env.sender` has sufficient pre-approval.
     TransferFrom {
@@ -57,6 +87,11 @@ pub enum Cw20ExecuteMsg {
     /// a new minter. Setting the minter to None will remove the
     /// token's minter forever.
     UpdateMinter { new_minter: Option<String> },
+    /// Only with the "mintable" extension. The current minter may register a contract that
+    /// receives a `Cw20BurnHookMsg` on every `Burn` and `BurnFrom`, and can veto the burn by
+    /// returning an error. Setting it to None removes the hook, which the hook contract itself
+    /// may also do. Without a minter, nobody else can change or remove the hook anymore.
+    UpdateBurnHook { hook: Option<String> },
     /// Only with the "marketing" extension. If authorized, updates marketing metadata.
     /// Setting None/null for any of these will leave it unchanged.
     /// Setting Some("") will clear this field on the contract storage
@@ -65,9 +100,21 @@ pub enum Cw20ExecuteMsg {
         project: Option<String>,
         /// A longer description of the token and it's utility. Designed for tooltips or such
         description: Option<String>,
-        /// The address (if any) who can update this data structure
+        /// Deprecated and ignored, use `UpdateMarketingAdmin` to change the marketing account.
         marketing: Option<String>,
     },
+    /// Only with the "marketing" extension. The current marketing account may hand over the
+    /// role to a new address. Setting it to None renounces the role forever, after which no
+    /// marketing info or logo can be changed anymore.
+    UpdateMarketingAdmin { admin: Option<String> },
     /// If set as the "marketing" role on the contract, upload a new URL, SVG, or PNG for the token
     UploadLogo(Logo),
 }
+
+/// A single entry of `Cw20ExecuteMsg::BatchSend`
+#[cw_serde]
+pub struct BatchSendItem {
+    pub contract: String,
+    pub amount: Uint128,
+    pub msg: Binary,
+}
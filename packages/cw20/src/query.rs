@@ -5,6 +5,7 @@ use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Binary, Uint128};
 
 use crate::logo::LogoInfo;
+use cw2::ContractVersion;
 use cw_utils::Expiration;
 
 #[cw_serde]
@@ -16,6 +17,9 @@ pub enum Cw20QueryMsg {
     /// Returns metadata on the contract - name, decimals, supply, etc.
     /// Return type: TokenInfoResponse.
     TokenInfo {},
+    /// Returns token info, minter, marketing info and contract version in one go.
+    /// Return type: FullTokenInfoResponse.
+    FullTokenInfo {},
     /// Only with "allowance" extension.
     /// Returns how much spender can use from owner account, 0 if unset.
     /// Return type: AllowanceResponse.
@@ -91,6 +95,19 @@ pub struct MarketingInfoResponse {
     pub marketing: Option<Addr>,
 }
 
+/// Combines the responses of `TokenInfo`, `Minter` and `MarketingInfo`, so clients
+/// can show a token with a single query.
+#[cw_serde]
+pub struct FullTokenInfoResponse {
+    pub token_info: TokenInfoResponse,
+    /// None if the token is not mintable
+    pub minter: Option<MinterResponse>,
+    /// None if no marketing info was ever set
+    pub marketing: Option<MarketingInfoResponse>,
+    /// None if the contract does not store a cw2 version
+    pub version: Option<ContractVersion>,
+}
+
 /// When we download an embedded logo, we get this response type.
 /// We expect a SPA to be able to accept this info and display it.
 #[cw_serde]
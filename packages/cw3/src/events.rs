@@ -0,0 +1,150 @@
+use cosmwasm_std::{to_json_string, Addr};
+use cw_utils::Expiration;
+use serde::Serialize;
+
+use crate::{Status, Vote};
+
+/// Governance events with a stable set of attributes, emitted by all cw3 contracts
+/// in this repo so indexers can follow proposals without knowing the implementation.
+///
+/// Every event carries a `proposal_id` attribute. Votes and statuses use the same
+/// lowercase names as in the JSON messages (eg. "yes", "passed").
+pub trait Cw3Event {
+    /// Builds the event to add to a `Response`. It is emitted as `wasm-<type>`.
+    fn to_event(&self) -> cosmwasm_std::Event;
+}
+
+/// Emitted when a proposal is created.
+/// Type: `cw3_proposal_open`, attributes: `proposal_id`, `proposer`, `expires`
+pub struct ProposalOpenEvent<'a> {
+    pub id: u64,
+    pub proposer: &'a Addr,
+    pub expires: Expiration,
+}
+
+impl Cw3Event for ProposalOpenEvent<'_> {
+    fn to_event(&self) -> cosmwasm_std::Event {
+        cosmwasm_std::Event::new("cw3_proposal_open")
+            .add_attribute("proposal_id", self.id.to_string())
+            .add_attribute("proposer", self.proposer)
+            .add_attribute("expires", self.expires.to_string())
+    }
+}
+
+/// Emitted for every ballot, including the implicit yes vote of the proposer.
+/// Type: `cw3_vote_cast`, attributes: `proposal_id`, `voter`, `vote`, `weight`
+pub struct VoteCastEvent<'a> {
+    pub id: u64,
+    pub voter: &'a Addr,
+    pub vote: Vote,
+    pub weight: u64,
+}
+
+impl Cw3Event for VoteCastEvent<'_> {
+    fn to_event(&self) -> cosmwasm_std::Event {
+        cosmwasm_std::Event::new("cw3_vote_cast")
+            .add_attribute("proposal_id", self.id.to_string())
+            .add_attribute("voter", self.voter)
+            .add_attribute("vote", lowercase(&self.vote))
+            .add_attribute("weight", self.weight.to_string())
+    }
+}
+
+/// Emitted whenever the stored status of a proposal changes.
+/// Type: `cw3_proposal_status_change`, attributes: `proposal_id`, `old_status`, `new_status`
+pub struct ProposalStatusChangeEvent {
+    pub id: u64,
+    pub old: Status,
+    pub new: Status,
+}
+
+impl Cw3Event for ProposalStatusChangeEvent {
+    fn to_event(&self) -> cosmwasm_std::Event {
+        cosmwasm_std::Event::new("cw3_proposal_status_change")
+            .add_attribute("proposal_id", self.id.to_string())
+            .add_attribute("old_status", lowercase(&self.old))
+            .add_attribute("new_status", lowercase(&self.new))
+    }
+}
+
+impl ProposalStatusChangeEvent {
+    /// Returns the event only if the status actually changed
+    pub fn if_changed(id: u64, old: Status, new: Status) -> Option<cosmwasm_std::Event> {
+        (old != new).then(|| Self { id, old, new }.to_event())
+    }
+}
+
+// the serde representation of both enums, without the JSON quotes
+fn lowercase(value: &impl Serialize) -> String {
+    to_json_string(value)
+        .unwrap_or_default()
+        .trim_matches('"')
+        .to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cosmwasm_std::attr;
+
+    #[test]
+    fn proposal_open_attributes() {
+        let proposer = Addr::unchecked("proposer");
+        let event = ProposalOpenEvent {
+            id: 7,
+            proposer: &proposer,
+            expires: Expiration::AtHeight(12345),
+        }
+        .to_event();
+        assert_eq!(event.ty, "cw3_proposal_open");
+        assert_eq!(
+            event.attributes,
+            vec![
+                attr("proposal_id", "7"),
+                attr("proposer", "proposer"),
+                attr("expires", "expiration height: 12345"),
+            ]
+        );
+    }
+
+    #[test]
+    fn vote_cast_attributes() {
+        let voter = Addr::unchecked("voter");
+        let event = VoteCastEvent {
+            id: 7,
+            voter: &voter,
+            vote: Vote::Abstain,
+            weight: 3,
+        }
+        .to_event();
+        assert_eq!(event.ty, "cw3_vote_cast");
+        assert_eq!(
+            event.attributes,
+            vec![
+                attr("proposal_id", "7"),
+                attr("voter", "voter"),
+                attr("vote", "abstain"),
+                attr("weight", "3"),
+            ]
+        );
+    }
+
+    #[test]
+    fn status_change_attributes() {
+        let event = ProposalStatusChangeEvent::if_changed(7, Status::Open, Status::Passed).unwrap();
+        assert_eq!(event.ty, "cw3_proposal_status_change");
+        assert_eq!(
+            event.attributes,
+            vec![
+                attr("proposal_id", "7"),
+                attr("old_status", "open"),
+                attr("new_status", "passed"),
+            ]
+        );
+
+        assert_eq!(
+            ProposalStatusChangeEvent::if_changed(7, Status::Open, Status::Open),
+            None
+        );
+    }
+}
@@ -57,6 +57,18 @@ impl Cw3Contract {
         self.encode_msg(msg)
     }
 
+    pub fn execute_multiple(
+        &self,
+        proposal_ids: Vec<u64>,
+        fail_fast: bool,
+    ) -> StdResult<CosmosMsg> {
+        let msg = Cw3ExecuteMsg::ExecuteMultiple {
+            proposal_ids,
+            fail_fast,
+        };
+        self.encode_msg(msg)
+    }
+
     pub fn close(&self, proposal_id: u64) -> StdResult<CosmosMsg> {
         let msg = Cw3ExecuteMsg::Close { proposal_id };
         self.encode_msg(msg)
@@ -11,12 +11,14 @@ For more information on this specification, please check out the
 
 // mod helpers;
 mod deposit;
+mod events;
 mod helpers;
 mod msg;
 mod proposal;
 mod query;
 
 pub use crate::deposit::{DepositError, DepositInfo, UncheckedDepositInfo};
+pub use crate::events::{Cw3Event, ProposalOpenEvent, ProposalStatusChangeEvent, VoteCastEvent};
 pub use crate::helpers::Cw3Contract;
 pub use crate::msg::{Cw3ExecuteMsg, Vote};
 pub use crate::proposal::{Ballot, Proposal, Votes};
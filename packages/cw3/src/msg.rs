@@ -26,6 +26,12 @@ where
     Execute {
         proposal_id: u64,
     },
+    /// Executes the given proposals in order. Proposals that cannot be executed are
+    /// reported in the attributes and skipped, unless `fail_fast` is set.
+    ExecuteMultiple {
+        proposal_ids: Vec<u64>,
+        fail_fast: bool,
+    },
     Close {
         proposal_id: u64,
     },